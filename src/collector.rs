@@ -0,0 +1,1853 @@
+use std::{collections::VecDeque, time::Duration};
+
+use evdev::{AbsInfo, AbsoluteAxisCode, EventSummary, InputEvent, KeyCode, SynchronizationCode};
+use slint::{
+    LogicalPosition, PhysicalPosition, SharedString,
+    platform::{Key, PointerEventButton, WindowEvent},
+};
+
+use crate::{
+    Config, CoordinateMode, Distance, DragEvent, Error, ExtendedEvent, GestureEvent, Modifiers,
+    MultiTouchEvent, Rounding, ScrollEdge, StylusEvent, StylusTool,
+};
+
+/// Upper limit on the number of multitouch slots tracked
+const MAX_SLOTS: usize = 64;
+
+/// The modifier keys, in the order of their bits in `Collector::modifier_keys`, with the slint
+/// keys they are reported as
+const MODIFIER_KEYS: [(KeyCode, Key); 8] = [
+    (KeyCode::KEY_LEFTSHIFT, Key::Shift),
+    (KeyCode::KEY_RIGHTSHIFT, Key::ShiftR),
+    (KeyCode::KEY_LEFTCTRL, Key::Control),
+    (KeyCode::KEY_RIGHTCTRL, Key::ControlR),
+    (KeyCode::KEY_LEFTALT, Key::Alt),
+    (KeyCode::KEY_RIGHTALT, Key::AltGr),
+    (KeyCode::KEY_LEFTMETA, Key::Meta),
+    (KeyCode::KEY_RIGHTMETA, Key::MetaR),
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum ButtonChange {
+    #[default]
+    None,
+    Up,
+    Down,
+    /// Contact went down and back up within the same frame
+    Tap,
+}
+
+/// Which events are used to decide when a contact begins and ends
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ContactSource {
+    /// Contact follows a key, normally `BTN_TOUCH`
+    Key(KeyCode),
+    /// Contact follows the `ABS_MT_TRACKING_ID` lifecycle of a type-B multitouch device, or the
+    /// contacts reported by a type-A device, for devices which never report `BTN_TOUCH`
+    TrackingId,
+}
+
+impl Default for ContactSource {
+    fn default() -> Self {
+        Self::Key(KeyCode::BTN_TOUCH)
+    }
+}
+
+/// The properties of a device which affect how its events are interpreted
+///
+/// These are normally read from the device when it is opened, but may be constructed directly
+/// when converting events without a device.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Capabilities {
+    /// Which events indicate contact
+    pub contact_source: ContactSource,
+    /// Range of the axis reporting X position, if known
+    pub x_info: Option<AbsInfo>,
+    /// Range of the axis reporting Y position, if known
+    pub y_info: Option<AbsInfo>,
+    /// True if the device reports contacts in multitouch slots
+    pub multitouch: bool,
+    /// True if the device reports multitouch contacts using protocol type A, in which each
+    /// contact is ended by `SYN_MT_REPORT` rather than being assigned a slot
+    pub type_a: bool,
+    /// True if the device reports stylus tilt
+    pub tilt: bool,
+}
+
+/// Counts of the events processed, for tuning filters and rate limits
+///
+/// Counting starts when the device is opened, and continues across
+/// [`reset_state()`](crate::SlintEventsWrapper::reset_state). Rates can be found by comparing
+/// counts taken at different times.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// Events read from the device, including `SYN_REPORT`s
+    pub raw_events: u64,
+    /// Window events produced by the conversion, including moves which were later coalesced
+    pub window_events: u64,
+    /// Moves replaced by a later move, by
+    /// [`coalesce_interval()`](crate::Config::coalesce_interval) or
+    /// [`max_move_rate()`](crate::Config::max_move_rate), or dropped by
+    /// [`event_queue_limit()`](crate::Config::event_queue_limit)
+    pub moves_coalesced: u64,
+    /// Positions discarded as outliers by [`ghost_filter()`](crate::Config::ghost_filter)
+    pub outliers_dropped: u64,
+}
+
+impl Capabilities {
+    /// Read the capabilities reported by the device
+    pub fn detect(device: &evdev::Device) -> Result<Self, Error> {
+        Self::detect_with_axes(device, AbsoluteAxisCode::ABS_X, AbsoluteAxisCode::ABS_Y)
+    }
+
+    /// Read the capabilities reported by the device, with position reported on the given axes
+    pub(crate) fn detect_with_axes(
+        device: &evdev::Device,
+        x_axis: AbsoluteAxisCode,
+        y_axis: AbsoluteAxisCode,
+    ) -> Result<Self, Error> {
+        let has_key = |key| {
+            device
+                .supported_keys()
+                .is_some_and(|keys| keys.contains(key))
+        };
+        let has_tracking_id = device
+            .supported_absolute_axes()
+            .is_some_and(|axes| axes.contains(AbsoluteAxisCode::ABS_MT_TRACKING_ID));
+        let has_axis = |axis| {
+            device
+                .supported_absolute_axes()
+                .is_some_and(|axes| axes.contains(axis))
+        };
+        let multitouch = has_axis(AbsoluteAxisCode::ABS_MT_SLOT);
+        // Without slots, multitouch positions can only be reported with protocol type A
+        let type_a = !multitouch && has_axis(AbsoluteAxisCode::ABS_MT_POSITION_X);
+
+        let contact_source = if has_key(KeyCode::BTN_TOUCH) {
+            ContactSource::Key(KeyCode::BTN_TOUCH)
+        } else if has_tracking_id || type_a {
+            ContactSource::TrackingId
+        } else if has_key(KeyCode::BTN_TOOL_FINGER) {
+            ContactSource::Key(KeyCode::BTN_TOOL_FINGER)
+        } else {
+            ContactSource::default()
+        };
+
+        let tilt = device.supported_absolute_axes().is_some_and(|axes| {
+            axes.contains(AbsoluteAxisCode::ABS_TILT_X)
+                || axes.contains(AbsoluteAxisCode::ABS_TILT_Y)
+        });
+
+        let mut caps = Self {
+            contact_source,
+            multitouch,
+            type_a,
+            tilt,
+            ..Default::default()
+        };
+        // Prefer the single touch axes, falling back to the multitouch axes
+        for (code, info) in device.get_absinfo()? {
+            match code {
+                code if code == x_axis => caps.x_info = Some(info),
+                code if code == y_axis => caps.y_info = Some(info),
+                AbsoluteAxisCode::ABS_MT_POSITION_X => {
+                    caps.x_info.get_or_insert(info);
+                }
+                AbsoluteAxisCode::ABS_MT_POSITION_Y => {
+                    caps.y_info.get_or_insert(info);
+                }
+                _ => (),
+            }
+        }
+        Ok(caps)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Axis {
+    X,
+    Y,
+}
+
+/// State of one multitouch slot
+#[derive(Clone, Copy, Debug)]
+struct Slot {
+    /// The tracking ID of the contact in the slot, or -1 if there is none
+    tracking_id: i32,
+    /// The tracking ID as of the end of the previous frame
+    reported_id: i32,
+    position: (i32, i32),
+    /// Whether the X and Y axes have each reported a value for the slot
+    axes_seen: (bool, bool),
+    press_position: (i32, i32),
+    /// True if the position has changed since the previous frame
+    moved: bool,
+    /// True if any `ABS_MT` event has referred to the slot during the current frame
+    reported_in_frame: bool,
+    /// Last reported `ABS_MT_PRESSURE` of the contact, if any
+    pressure: Option<i32>,
+}
+
+impl Default for Slot {
+    fn default() -> Self {
+        Self {
+            tracking_id: -1,
+            reported_id: -1,
+            position: (0, 0),
+            axes_seen: (false, false),
+            press_position: (0, 0),
+            moved: false,
+            reported_in_frame: false,
+            pressure: None,
+        }
+    }
+}
+
+/// A contact reported by a type-A multitouch device, accumulated until `SYN_MT_REPORT`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct TypeAContact {
+    tracking_id: Option<i32>,
+    x: Option<i32>,
+    y: Option<i32>,
+    pressure: Option<i32>,
+}
+
+/// Collect evdev events and convert them to slint events
+pub(crate) struct Collector {
+    config: Config,
+    /// A configuration set during a frame, to be applied when the frame ends
+    pending_config: Option<Config>,
+    /// While paused, the button which the window last saw pressed, if any
+    paused: Option<Option<PointerEventButton>>,
+    last_position: (i32, i32),
+    /// Whether the X and Y axes have each reported a value since the collector was created
+    axes_seen: (bool, bool),
+    /// The position reported by the most recently emitted `PointerMoved`
+    last_move_position: Option<(i32, i32)>,
+    /// The position at which the current contact was pressed
+    press_position: (i32, i32),
+    button_change: ButtonChange,
+    /// Events generated ahead of the device's events, such as the release of a tap, to be
+    /// returned next, in order
+    queued: VecDeque<WindowEvent>,
+    /// The contact keys which are currently down
+    contact_keys_down: Vec<KeyCode>,
+    /// True if the contact keys indicate contact
+    contact: bool,
+    /// True if `BTN_TOUCH` has been reported as a contact key
+    touch_reported: bool,
+    /// The button of a press which has been reported without a matching release
+    ///
+    /// The release always reports the same button as the press, even if the modifier or the
+    /// config has since changed.
+    pressed: Option<PointerEventButton>,
+    /// True if the configured modifier key is down
+    modifier_down: bool,
+    /// The modifier keys which are down, one bit for each of `MODIFIER_KEYS`
+    modifier_keys: u8,
+    /// The position last reported by a scroll, while a contact pressed in the scroll strip is down
+    scroll_from: Option<(i32, i32)>,
+    /// The smoothed device position, while smoothing is configured
+    smoothed: Option<(f32, f32)>,
+    /// True if the current scroll was started in the scroll surface, and so scrolls horizontally
+    /// as well as vertically
+    scroll_horizontal: bool,
+    /// True if a move has been emitted since the current contact was pressed
+    moved_since_press: bool,
+    /// True if a contact is down and has not yet left the tap deadzone
+    in_deadzone: bool,
+    /// True if the current contact was pressed within the edge margin, and has not yet been
+    /// reported
+    press_suppressed: bool,
+    /// The most recent position accepted by the ghost filter
+    accepted_position: Option<(i32, i32)>,
+    /// True if the current position is a possible outlier held by the ghost filter
+    ghost_suspect: bool,
+    caps: Capabilities,
+    /// True after `SYN_DROPPED`, until the events of the incomplete frame have been discarded
+    dropping: bool,
+    /// True if events have been pushed since the last `SYN_REPORT`
+    frame_open: bool,
+    /// The multitouch slot which subsequent ABS_MT events refer to
+    current_slot: i32,
+    /// The multitouch slot whose contact is reported as the pointer
+    active_slot: Option<i32>,
+    slots: Vec<Slot>,
+    /// The type-A contact currently being reported
+    type_a_contact: TypeAContact,
+    /// The type-A contacts reported so far in the current frame
+    type_a_contacts: Vec<TypeAContact>,
+    /// Per-contact events generated by the most recent frame
+    multitouch_events: VecDeque<MultiTouchEvent>,
+    /// Gesture events generated by the most recent frame
+    gesture_events: VecDeque<GestureEvent>,
+    /// The tap grid cell of a release in the current frame, to be reported as a gesture
+    tapped_cell: Option<(usize, usize)>,
+    /// The number of contacts as of the end of the previous frame
+    contact_count: usize,
+    /// Last reported `ABS_PRESSURE`, if any
+    pressure: Option<i32>,
+    /// Last reported `ABS_DISTANCE`, if any
+    distance: Option<i32>,
+    /// Last reported `ABS_TILT_X` and `ABS_TILT_Y`
+    tilt: (i32, i32),
+    /// The tool most recently brought into range
+    tool: StylusTool,
+    /// Deflection of the stick axes, each in the range -1.0..=1.0
+    stick: (f32, f32),
+    /// The exact logical position last set by the stick, which may lie between raw positions
+    stick_position: Option<LogicalPosition>,
+    metrics: Metrics,
+    /// The sequence number of the next sequenced event
+    sequence: u64,
+}
+
+impl Collector {
+    pub fn new(config: Config, caps: Capabilities) -> Self {
+        let park = config
+            .park_position
+            .map(|position| WindowEvent::PointerMoved { position });
+        let mut collector = Self {
+            config: config.validated(),
+            pending_config: None,
+            paused: None,
+            last_position: (0, 0),
+            axes_seen: (false, false),
+            last_move_position: None,
+            press_position: (0, 0),
+            button_change: ButtonChange::None,
+            queued: park.into_iter().collect(),
+            contact_keys_down: Vec::new(),
+            contact: false,
+            touch_reported: false,
+            pressed: None,
+            modifier_down: false,
+            modifier_keys: 0,
+            scroll_from: None,
+            scroll_horizontal: false,
+            smoothed: None,
+            moved_since_press: false,
+            in_deadzone: false,
+            press_suppressed: false,
+            accepted_position: None,
+            ghost_suspect: false,
+            caps,
+            dropping: false,
+            frame_open: false,
+            current_slot: 0,
+            active_slot: None,
+            slots: Vec::new(),
+            type_a_contact: TypeAContact::default(),
+            type_a_contacts: Vec::new(),
+            multitouch_events: VecDeque::new(),
+            gesture_events: VecDeque::new(),
+            tapped_cell: None,
+            contact_count: 0,
+            pressure: None,
+            distance: None,
+            tilt: (0, 0),
+            tool: StylusTool::Unknown,
+            stick: (0.0, 0.0),
+            stick_position: None,
+            metrics: Metrics::default(),
+            sequence: 0,
+        };
+        if let Some(position) = collector.config.initial_position {
+            collector.last_position = collector.raw_position(position);
+            collector.axes_seen = (true, true);
+        }
+        collector
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Replace the configuration, keeping the tracked state
+    ///
+    /// While a frame is in progress, the change is held until the frame ends, so that each frame
+    /// is converted with a single configuration.
+    pub fn set_config(&mut self, config: Config) {
+        if self.frame_open {
+            self.pending_config = Some(config);
+        } else {
+            self.apply_config(config);
+        }
+    }
+
+    /// Switch to a new configuration
+    ///
+    /// Logical positions are derived from the raw position as needed, so the only one kept is the
+    /// stick's exact position, which is then derived again from the raw position.
+    fn apply_config(&mut self, config: Config) {
+        self.config = config.validated();
+        self.stick_position = None;
+    }
+
+    /// Discard all tracked state, as if no events had been received
+    pub fn reset(&mut self) {
+        // The device only reports the slot and tool when they change, so they remain valid
+        let current_slot = self.current_slot;
+        let tool = self.tool;
+        let modifier_keys = self.modifier_keys;
+        let metrics = self.metrics;
+        let sequence = self.sequence;
+        let paused = self.paused;
+        let config = self
+            .pending_config
+            .take()
+            .unwrap_or_else(|| self.config.clone());
+        *self = Self::new(config, self.caps);
+        self.sequence = sequence;
+        self.paused = paused;
+        self.current_slot = current_slot;
+        self.tool = tool;
+        self.modifier_keys = modifier_keys;
+        self.metrics = metrics;
+    }
+
+    /// Replace the device capabilities, e.g. after a device has been reopened
+    pub fn set_capabilities(&mut self, caps: Capabilities) {
+        self.caps = caps;
+    }
+
+    pub fn capabilities(&self) -> &Capabilities {
+        &self.caps
+    }
+
+    /// Push events from `events` until a window event is produced
+    pub fn next_from(
+        &mut self,
+        events: &mut impl Iterator<Item = InputEvent>,
+    ) -> Option<WindowEvent> {
+        if let Some(event) = self.take_queued() {
+            return Some(event);
+        }
+        events.find_map(|event| self.push(event.destructure()))
+    }
+
+    /// Push events from `events` up to the end of the next frame, returning the raw events
+    /// pushed alongside the window event they produced
+    ///
+    /// An event produced without pushing further events, e.g. at the end of the events, is
+    /// returned with no raw events.
+    pub fn next_frame_from(
+        &mut self,
+        events: &mut impl Iterator<Item = InputEvent>,
+    ) -> Option<(Vec<EventSummary>, Option<WindowEvent>)> {
+        if let Some(event) = self.take_queued() {
+            return Some((Vec::new(), Some(event)));
+        }
+        let mut raw = Vec::new();
+        for event in events.by_ref() {
+            let window_event = self.push(event.destructure());
+            raw.push(event.destructure());
+            let end = matches!(
+                raw.last(),
+                Some(EventSummary::Synchronization(_, code, _)) if self.is_frame_end(*code)
+            );
+            if window_event.is_some() || end {
+                return Some((raw, window_event));
+            }
+        }
+        // The end of the events is treated as the end of a read from a device
+        let window_event = self.flush_read();
+        if raw.is_empty() && window_event.is_none() {
+            return None;
+        }
+        Some((raw, window_event))
+    }
+
+    /// Take an event generated by an earlier push which has not yet been returned
+    ///
+    /// This must be checked before pushing further events.
+    pub fn take_queued(&mut self) -> Option<WindowEvent> {
+        let event = self.queued.pop_front();
+        if event.is_some() {
+            self.metrics.window_events += 1;
+        }
+        event
+    }
+
+    /// Take all the events generated by earlier pushes which have not yet been returned
+    pub fn drain_queued(&mut self) -> impl Iterator<Item = WindowEvent> + '_ {
+        std::iter::from_fn(|| self.take_queued())
+    }
+
+    /// True if an event is waiting to be returned without reading from the device
+    pub fn has_queued(&self) -> bool {
+        !self.queued.is_empty()
+    }
+
+    /// Push the next of `events`, returning any window event produced
+    ///
+    /// When `events` runs out, the frame is ended as for the end of a read with
+    /// [`Config::flush_on_read()`](crate::Config::flush_on_read), once, setting `flushed`. Returns
+    /// `None` after that.
+    fn push_next_or_flush(
+        &mut self,
+        events: &mut impl Iterator<Item = InputEvent>,
+        flushed: &mut bool,
+    ) -> Option<Option<WindowEvent>> {
+        match events.next() {
+            Some(event) => Some(self.push(event.destructure())),
+            None if !*flushed => {
+                *flushed = true;
+                Some(self.flush_read())
+            }
+            None => None,
+        }
+    }
+
+    /// Push events from `events` until a per-contact event is produced
+    ///
+    /// Devices without multitouch slots report their single contact with an id of 0.
+    pub fn next_multitouch_from(
+        &mut self,
+        events: &mut impl Iterator<Item = InputEvent>,
+    ) -> Option<MultiTouchEvent> {
+        let mut flushed = false;
+        loop {
+            if let Some(event) = self.multitouch_events.pop_front() {
+                return Some(event);
+            }
+            let window_event = match self.take_queued() {
+                Some(event) => Some(event),
+                None => self.push_next_or_flush(events, &mut flushed)?,
+            };
+            if !self.has_slots()
+                && let Some(event) = window_event
+            {
+                return Some(MultiTouchEvent {
+                    id: 0,
+                    event,
+                    pressure: self.pressure,
+                });
+            }
+        }
+    }
+
+    /// Push events from `events` until an extended event is produced
+    pub fn next_extended_from(
+        &mut self,
+        events: &mut impl Iterator<Item = InputEvent>,
+    ) -> Option<ExtendedEvent> {
+        let mut flushed = false;
+        loop {
+            if let Some(event) = self.gesture_events.pop_front() {
+                return Some(ExtendedEvent::Gesture(event));
+            }
+            let event = match self.take_queued() {
+                Some(event) => event,
+                None => match self.push_next_or_flush(events, &mut flushed)? {
+                    Some(event) => event,
+                    None => continue,
+                },
+            };
+            let is_pen = matches!(self.tool, StylusTool::Pen | StylusTool::Eraser);
+            return if self.caps.tilt || is_pen {
+                Some(ExtendedEvent::Stylus(StylusEvent {
+                    event,
+                    tool: self.tool,
+                    pressure: self.pressure,
+                    tilt_x: self.tilt.0,
+                    tilt_y: self.tilt.1,
+                }))
+            } else if let (true, WindowEvent::PointerMoved { position }, Some(button)) =
+                (self.config.drag_events, &event, self.pressed)
+            {
+                Some(ExtendedEvent::Drag(DragEvent {
+                    position: *position,
+                    button,
+                }))
+            } else {
+                Some(ExtendedEvent::Window(event))
+            };
+        }
+    }
+
+    pub fn metrics(&self) -> Metrics {
+        self.metrics
+    }
+
+    /// Take the sequence number for an event being returned
+    pub fn next_sequence(&mut self) -> u64 {
+        let sequence = self.sequence;
+        self.sequence += 1;
+        sequence
+    }
+
+    /// Count a move which was replaced by a later one before being returned
+    #[cfg(feature = "tokio")]
+    pub fn count_coalesced_move(&mut self) {
+        self.metrics.moves_coalesced += 1;
+    }
+
+    pub fn push(&mut self, event: evdev::EventSummary) -> Option<WindowEvent> {
+        self.metrics.raw_events += 1;
+        let event = self.push_event(event);
+        if event.is_some() {
+            self.metrics.window_events += 1;
+        }
+        event
+    }
+
+    fn push_event(&mut self, event: evdev::EventSummary) -> Option<WindowEvent> {
+        if self.dropping {
+            // The events up to and including the end of the next frame are incomplete
+            if let EventSummary::Synchronization(_, code, _) = event
+                && self.is_frame_end(code)
+            {
+                self.dropping = false;
+            }
+            return None;
+        }
+        self.frame_open = true;
+        if let Some(handler) = &self.config.position_handler {
+            (handler.0)(&event, &mut self.last_position);
+        }
+        if let EventSummary::Key(_event, key, value) = &event {
+            self.push_tool_key(*key, *value);
+            if let Some(event) = self.push_modifier_key(*key, *value) {
+                return Some(event);
+            }
+        }
+        if let EventSummary::AbsoluteAxis(_event, code, value) = event
+            && self.push_stick_axis(code, value)
+        {
+            return None;
+        }
+        match event {
+            EventSummary::Synchronization(_, SynchronizationCode::SYN_MT_REPORT, _)
+                if self.caps.type_a =>
+            {
+                self.finish_type_a_contact();
+                if self.is_frame_end(SynchronizationCode::SYN_MT_REPORT) {
+                    return self.end_frame();
+                }
+            }
+            EventSummary::Synchronization(_, SynchronizationCode::SYN_DROPPED, _) => {
+                self.dropping = true;
+            }
+            EventSummary::Synchronization(_, code, _) if self.is_frame_end(code) => {
+                return self.end_frame();
+            }
+            EventSummary::AbsoluteAxis(_event, code, value) => match code {
+                code if code == self.config.x_axis => self.set_device_axis(Axis::X, value),
+                code if code == self.config.y_axis => self.set_device_axis(Axis::Y, value),
+                AbsoluteAxisCode::ABS_MT_TRACKING_ID if self.caps.type_a => {
+                    self.type_a_contact.tracking_id = Some(value);
+                }
+                AbsoluteAxisCode::ABS_MT_POSITION_X if self.caps.type_a => {
+                    self.type_a_contact.x = Some(value);
+                }
+                AbsoluteAxisCode::ABS_MT_POSITION_Y if self.caps.type_a => {
+                    self.type_a_contact.y = Some(value);
+                }
+                AbsoluteAxisCode::ABS_MT_PRESSURE if self.caps.type_a => {
+                    self.type_a_contact.pressure = Some(value);
+                }
+                AbsoluteAxisCode::ABS_MT_SLOT => self.current_slot = value,
+                AbsoluteAxisCode::ABS_MT_TRACKING_ID => self.push_tracking_id(value),
+                AbsoluteAxisCode::ABS_MT_POSITION_X => self.push_mt_position(Axis::X, value),
+                AbsoluteAxisCode::ABS_MT_POSITION_Y => self.push_mt_position(Axis::Y, value),
+                AbsoluteAxisCode::ABS_MT_PRESSURE => {
+                    if let Some(slot) = self.current_slot_mut() {
+                        slot.pressure = Some(value);
+                    }
+                }
+                AbsoluteAxisCode::ABS_PRESSURE => self.pressure = Some(value),
+                AbsoluteAxisCode::ABS_DISTANCE => self.distance = Some(value),
+                AbsoluteAxisCode::ABS_TILT_X => self.tilt.0 = value,
+                AbsoluteAxisCode::ABS_TILT_Y => self.tilt.1 = value,
+                _ => (),
+            },
+            EventSummary::Key(_event, key, value) if self.is_contact_key(key) => {
+                self.push_contact_key(key, value)
+            }
+            EventSummary::Key(_event, key, value)
+                if self
+                    .config
+                    .modifier_button
+                    .is_some_and(|(modifier, _)| modifier == key) =>
+            {
+                self.modifier_down = value != 0;
+            }
+            _ => (),
+        }
+        if let EventSummary::AbsoluteAxis(_event, code, _value) = event
+            && (AbsoluteAxisCode::ABS_MT_SLOT.0..=AbsoluteAxisCode::ABS_MT_TOOL_Y.0)
+                .contains(&code.0)
+            && let Some(slot) = self.current_slot_mut()
+        {
+            slot.reported_in_frame = true;
+        }
+        None
+    }
+
+    /// True if `code` ends a frame, as configured
+    fn is_frame_end(&self, code: SynchronizationCode) -> bool {
+        code != SynchronizationCode::SYN_DROPPED && self.config.frame_end_codes.contains(&code)
+    }
+
+    /// End the current frame, as on `SYN_REPORT`
+    fn end_frame(&mut self) -> Option<WindowEvent> {
+        self.frame_open = false;
+        if self.caps.type_a {
+            self.finish_type_a_frame();
+        } else if self.config.lift_unreported_slots {
+            self.lift_unreported_slots();
+        }
+        self.finish_multitouch_frame();
+        let event = self.finish_frame();
+        self.finish_gesture_frame();
+        if let Some(config) = self.pending_config.take() {
+            self.apply_config(config);
+        }
+        if self.paused.is_some() {
+            self.queued.clear();
+            self.multitouch_events.clear();
+            self.gesture_events.clear();
+            return None;
+        }
+        event
+    }
+
+    /// Stop generating window events, while still tracking the position and contact
+    ///
+    /// `undelivered` are events already generated which will not be delivered to the window, in
+    /// order, along with any queued here. They are discarded, and the first press or release
+    /// among them shows whether the window last saw a contact pressed.
+    pub fn pause(&mut self, undelivered: impl IntoIterator<Item = WindowEvent>) {
+        if self.paused.is_some() {
+            return;
+        }
+        let seen = undelivered
+            .into_iter()
+            .chain(std::mem::take(&mut self.queued))
+            .find_map(|event| match event {
+                WindowEvent::PointerPressed { .. } => Some(None),
+                WindowEvent::KeyPressed { text } if self.is_contact_text(&text) => Some(None),
+                WindowEvent::PointerReleased { button, .. } => Some(Some(button)),
+                WindowEvent::KeyReleased { text } if self.is_contact_text(&text) => {
+                    Some(Some(PointerEventButton::Left))
+                }
+                _ => None,
+            });
+        self.paused = Some(seen.unwrap_or(self.pressed));
+    }
+
+    /// Generate window events again, returning the event which brings the window up to date with
+    /// the contact
+    ///
+    /// This is a release if the contact was lifted while paused, the press of a contact pressed
+    /// while paused, and otherwise a move to the current position. Returns `None` if not paused.
+    pub fn resume(&mut self) -> Option<WindowEvent> {
+        let seen = self.paused.take()?;
+        match (seen, self.pressed) {
+            (Some(button), None) => Some(match &self.config.contact_as_key {
+                Some(text) => WindowEvent::KeyReleased { text: text.clone() },
+                None => WindowEvent::PointerReleased {
+                    position: self.last_logical_position(),
+                    button,
+                },
+            }),
+            (None, Some(_)) => self.replay_state(),
+            _ if self.config.contact_as_key.is_some() || !self.position_known() => None,
+            _ => Some(WindowEvent::PointerMoved {
+                position: self.last_logical_position(),
+            }),
+        }
+    }
+
+    /// Release the pressed contact, although the device still reports it, e.g. when it is stuck
+    ///
+    /// The contact is then treated as hovering until the device reports it lifted.
+    #[cfg(feature = "tokio")]
+    pub fn force_release(&mut self) -> Option<WindowEvent> {
+        self.pressed?;
+        let event = self.release();
+        self.metrics.window_events += 1;
+        Some(event)
+    }
+
+    /// True while window events are not generated
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    /// End a frame left open at the end of a read from the device, if configured to
+    pub fn flush_read(&mut self) -> Option<WindowEvent> {
+        if !self.config.flush_on_read || !self.frame_open || self.dropping {
+            return None;
+        }
+        let event = self.end_frame();
+        if event.is_some() {
+            self.metrics.window_events += 1;
+        }
+        event
+    }
+
+    /// Generate the pointer event for the end of a frame
+    fn finish_frame(&mut self) -> Option<WindowEvent> {
+        // Nothing is reported until the position is known, since (0, 0) may be a real position.
+        // Contact changes are kept until then.
+        if !self.position_known() {
+            return None;
+        }
+        if matches!(self.button_change, ButtonChange::Down | ButtonChange::Tap) {
+            self.use_contact_position();
+        }
+        // Events are generated from the smoothed position, while the device position is kept for
+        // the axes which the next frame does not report
+        let device_position = self.last_position;
+        if let Some(alpha) = self.config.smoothing {
+            self.last_position = self.smooth(alpha);
+        }
+        let event = self.finish_pointer_frame();
+        self.last_position = device_position;
+        event
+    }
+
+    /// Apply the smoothing filter to the device position at the end of a frame
+    ///
+    /// The filter restarts from the device position when a contact is pressed, so that a stroke
+    /// does not start with a lag.
+    fn smooth(&mut self, alpha: f32) -> (i32, i32) {
+        let (x, y) = (self.last_position.0 as f32, self.last_position.1 as f32);
+        let starts_contact = matches!(self.button_change, ButtonChange::Down | ButtonChange::Tap);
+        let average = match self.smoothed {
+            Some((avg_x, avg_y)) if !starts_contact => {
+                (avg_x + alpha * (x - avg_x), avg_y + alpha * (y - avg_y))
+            }
+            _ => (x, y),
+        };
+        self.smoothed = Some(average);
+        (average.0.round() as i32, average.1.round() as i32)
+    }
+
+    /// Generate the pointer event for the end of a frame, once the position is known
+    fn finish_pointer_frame(&mut self) -> Option<WindowEvent> {
+        // A new contact while one is still pressed means that its lift was missed, so the pressed
+        // contact is released where it was last reported before the new one is pressed, or tapped
+        if matches!(self.button_change, ButtonChange::Down | ButtonChange::Tap)
+            && self.pressed.is_some()
+        {
+            let position = self.last_position;
+            self.last_position = self.accepted_position.unwrap_or(position);
+            let release = self.release();
+            self.last_position = position;
+            // The new contact's events follow the release, ahead of anything already queued
+            if let Some(event) = self.finish_pointer_frame() {
+                self.queued.push_front(event);
+            }
+            return Some(release);
+        }
+        let starts_scroll = matches!(self.button_change, ButtonChange::Down | ButtonChange::Tap)
+            && self.pressed.is_none()
+            && (self.in_scroll_strip(self.last_position)
+                || self.in_scroll_surface(self.last_position));
+        if starts_scroll || self.scroll_from.is_some() {
+            return self.finish_scroll_frame();
+        }
+        // Contact changes are only acted on at the end of a frame, so a press is always reported at
+        // the coordinates from its own frame regardless of event order within the frame
+        // Presses and releases are only reported once each, however the contact changed within
+        // the frame
+        let button_change = match self.button_change {
+            ButtonChange::Up if self.pressed.is_none() && !self.press_suppressed => {
+                ButtonChange::None
+            }
+            button_change => button_change,
+        };
+        self.button_change = ButtonChange::None;
+        // A captured contact is followed anywhere on the screen
+        let captured = self.config.capture_on_press && self.pressed.is_some();
+        let in_active_area = captured || self.in_active_area(self.last_position);
+        match button_change {
+            ButtonChange::Down if !in_active_area => {
+                // Suppress the touch until it moves into the active area
+                self.press_suppressed = true;
+                None
+            }
+            ButtonChange::Down => Some(self.press()),
+            ButtonChange::Up if self.press_suppressed => {
+                self.press_suppressed = false;
+                None
+            }
+            ButtonChange::Up => Some(self.release()),
+            ButtonChange::Tap if !in_active_area => None,
+            ButtonChange::Tap => {
+                // Report both halves of a tap which was too fast to span frames
+                let press = self.press();
+                let release = self.release();
+                self.queued.push_back(release);
+                Some(press)
+            }
+            ButtonChange::None if !in_active_area => None,
+            ButtonChange::None if self.press_suppressed => {
+                self.press_suppressed = false;
+                Some(self.press())
+            }
+            ButtonChange::None if self.pressed.is_none() && self.out_of_hover_range() => None,
+            ButtonChange::None => self.moved(),
+        }
+    }
+
+    /// True if a hovering pen is further from the surface than the configured hover distance
+    fn out_of_hover_range(&self) -> bool {
+        matches!(
+            (self.config.hover_distance, self.distance),
+            (Some(max), Some(distance)) if distance > max
+        )
+    }
+
+    /// Take the position of a press from the pointer's multitouch contact, if it has one
+    ///
+    /// Some devices report a hovering tool on `ABS_X` and `ABS_Y` and the contact on the
+    /// multitouch axes, in which case the hover position may be reported after the contact
+    /// position in the frame of the press.
+    fn use_contact_position(&mut self) {
+        if self.config.position_handler.is_some() {
+            return;
+        }
+        let slot = self
+            .active_slot
+            .and_then(|index| usize::try_from(index).ok())
+            .and_then(|index| self.slots.get(index));
+        if let Some(slot) = slot
+            && slot.axes_seen == (true, true)
+        {
+            self.last_position = slot.position;
+        }
+    }
+
+    /// Generate the scroll event for the end of a frame, for a contact pressed in the scroll strip
+    /// or surface
+    fn finish_scroll_frame(&mut self) -> Option<WindowEvent> {
+        match std::mem::take(&mut self.button_change) {
+            ButtonChange::Down => {
+                self.scroll_from = Some(self.last_position);
+                self.scroll_horizontal = self.in_scroll_surface(self.last_position);
+                None
+            }
+            ButtonChange::Up | ButtonChange::Tap => {
+                self.scroll_from = None;
+                None
+            }
+            ButtonChange::None => {
+                let from = self.exact_logical_position(self.scroll_from?);
+                let to = self.exact_logical_position(self.last_position);
+                self.scroll_from = Some(self.last_position);
+                let delta_x = if self.scroll_horizontal {
+                    to.x - from.x
+                } else {
+                    0.0
+                };
+                let delta_y = to.y - from.y;
+                if delta_x == 0.0 && delta_y == 0.0 {
+                    return None;
+                }
+                Some(WindowEvent::PointerScrolled {
+                    position: self.last_logical_position(),
+                    delta_x,
+                    delta_y,
+                })
+            }
+        }
+    }
+
+    /// True if a raw position is within the configured scroll strip
+    fn in_scroll_strip(&self, position: (i32, i32)) -> bool {
+        let Some((edge, width)) = self.config.edge_scroll else {
+            return false;
+        };
+        let x = self.exact_logical_position(position).x;
+        let (x_info, y_info) = self.position_axis_info();
+        let range = x_info.zip(y_info);
+        match edge {
+            ScrollEdge::Left => {
+                let left = range.map_or(0.0, |(x_info, y_info)| {
+                    self.exact_logical_position((x_info.minimum(), y_info.minimum()))
+                        .x
+                });
+                x < left + width
+            }
+            ScrollEdge::Right => range.is_some_and(|(x_info, y_info)| {
+                x > self
+                    .exact_logical_position((x_info.maximum(), y_info.maximum()))
+                    .x
+                    - width
+            }),
+        }
+    }
+
+    /// True if a raw position is within the configured scroll surface
+    fn in_scroll_surface(&self, position: (i32, i32)) -> bool {
+        let Some(surface) = self.config.scroll_surface else {
+            return false;
+        };
+        let position = self.exact_logical_position(position);
+        (surface.x..surface.x + surface.width).contains(&position.x)
+            && (surface.y..surface.y + surface.height).contains(&position.y)
+    }
+
+    fn press(&mut self) -> WindowEvent {
+        self.press_position = self.last_position;
+        self.moved_since_press = false;
+        self.accepted_position = Some(self.last_position);
+        self.ghost_suspect = false;
+        self.in_deadzone = self.config.tap_deadzone.is_some();
+        let button = match self.config.modifier_button {
+            Some((_, button)) if self.modifier_down => button,
+            _ => self.config.default_button,
+        };
+        self.pressed = Some(button);
+        if let Some(text) = &self.config.contact_as_key {
+            return WindowEvent::KeyPressed { text: text.clone() };
+        }
+        WindowEvent::PointerPressed {
+            position: self.last_logical_position(),
+            button,
+        }
+    }
+
+    /// The press of the current contact, reported again at its current position
+    pub fn replay_state(&self) -> Option<WindowEvent> {
+        let button = self.pressed?;
+        if let Some(text) = &self.config.contact_as_key {
+            return Some(WindowEvent::KeyPressed { text: text.clone() });
+        }
+        Some(WindowEvent::PointerPressed {
+            position: self.last_logical_position(),
+            button,
+        })
+    }
+
+    /// Collapse the window events converted from a backlog into the one event which leaves the
+    /// window in the same state, given whether a contact was pressed before the backlog
+    ///
+    /// A press or release is kept if the backlog changed whether a contact is pressed, and
+    /// otherwise the backlog collapses to a move to its final position.
+    pub fn collapse(
+        &self,
+        events: impl IntoIterator<Item = WindowEvent>,
+        was_pressed: bool,
+    ) -> Option<WindowEvent> {
+        let mut latest = None;
+        let mut release = None;
+        for event in events {
+            match &event {
+                // Forwarded modifier keys do not change whether a contact is pressed
+                WindowEvent::KeyPressed { text } | WindowEvent::KeyReleased { text }
+                    if !self.is_contact_text(text) =>
+                {
+                    continue;
+                }
+                WindowEvent::PointerReleased { .. } | WindowEvent::KeyReleased { .. } => {
+                    release = Some(event.clone());
+                }
+                _ => (),
+            }
+            latest = Some(event);
+        }
+        match (was_pressed, self.pressed.is_some()) {
+            (false, true) => self.replay_state(),
+            (true, false) => release,
+            _ => match latest? {
+                WindowEvent::PointerPressed { position, .. }
+                | WindowEvent::PointerReleased { position, .. }
+                | WindowEvent::PointerMoved { position }
+                | WindowEvent::PointerScrolled { position, .. } => {
+                    Some(WindowEvent::PointerMoved { position })
+                }
+                _ => None,
+            },
+        }
+    }
+
+    fn release(&mut self) -> WindowEvent {
+        let button = self.pressed.take().unwrap_or(self.config.default_button);
+        self.in_deadzone = false;
+        if self.ghost_suspect
+            && let Some(accepted) = self.accepted_position
+        {
+            // The outlier was never confirmed
+            self.last_position = accepted;
+            self.metrics.outliers_dropped += 1;
+        }
+        self.ghost_suspect = false;
+        let cell = self.grid_cell(self.last_position);
+        if cell.is_some() && cell == self.grid_cell(self.press_position) {
+            self.tapped_cell = cell;
+        }
+        if let Some(text) = &self.config.contact_as_key {
+            return WindowEvent::KeyReleased { text: text.clone() };
+        }
+        let reported = self.last_reported_position();
+        let position = if self.config.release_at_press {
+            self.press_position
+        } else if let Some(threshold) = self.config.release_drift
+            && self.exceeds(reported, self.last_position, threshold)
+        {
+            reported
+        } else {
+            self.last_position
+        };
+        WindowEvent::PointerReleased {
+            position: self.logical_position(position),
+            button,
+        }
+    }
+
+    fn moved(&mut self) -> Option<WindowEvent> {
+        if self.config.contact_as_key.is_some() {
+            return None;
+        }
+        if let (Some(threshold), Some(accepted)) =
+            (self.config.ghost_filter, self.accepted_position)
+            && !self.ghost_suspect
+            && self.exceeds(accepted, self.last_position, threshold)
+        {
+            // Hold the sample until the next one shows whether it is an outlier
+            self.ghost_suspect = true;
+            return None;
+        }
+        if let (Some(threshold), Some(accepted)) =
+            (self.config.ghost_filter, self.accepted_position)
+            && self.ghost_suspect
+            && !self.exceeds(accepted, self.last_position, threshold)
+        {
+            // The held sample was not confirmed by this one
+            self.metrics.outliers_dropped += 1;
+        }
+        self.ghost_suspect = false;
+        self.accepted_position = Some(self.last_position);
+        if self.in_deadzone {
+            let deadzone = self.config.tap_deadzone?;
+            if !self.exceeds(self.press_position, self.last_position, deadzone) {
+                return None;
+            }
+            self.in_deadzone = false;
+        }
+        if self.config.suppress_move_at_press
+            && self.pressed.is_some()
+            && !self.moved_since_press
+            && self.last_position == self.press_position
+        {
+            return None;
+        }
+        if let Some(distance) = self.config.min_move_distance
+            && self.pressed.is_some()
+            && !self.exceeds(self.last_reported_position(), self.last_position, distance)
+        {
+            return None;
+        }
+        if !self.config.emit_idle_moves && self.last_move_position == Some(self.last_position) {
+            return None;
+        }
+        self.last_move_position = Some(self.last_position);
+        self.moved_since_press = true;
+        Some(WindowEvent::PointerMoved {
+            position: self.last_logical_position(),
+        })
+    }
+
+    /// The position of the last move reported for the pressed contact, if any, or else its press
+    fn last_reported_position(&self) -> (i32, i32) {
+        self.last_move_position
+            .filter(|_| self.moved_since_press)
+            .unwrap_or(self.press_position)
+    }
+
+    /// True if the distance between two raw positions is greater than `distance`
+    fn exceeds(&self, from: (i32, i32), to: (i32, i32), distance: Distance) -> bool {
+        let pixels = match distance {
+            Distance::Pixels(pixels) => pixels,
+            Distance::Millimeters {
+                mm,
+                fallback_pixels,
+            } => {
+                let (x_info, y_info) = self.position_axis_info();
+                let resolution = |info: Option<AbsInfo>| {
+                    info.map(|info| info.resolution())
+                        .filter(|&resolution| resolution > 0)
+                };
+                match (resolution(x_info), resolution(y_info)) {
+                    (Some(x_resolution), Some(y_resolution)) => {
+                        let dx = (to.0 - from.0) as f32 / x_resolution as f32;
+                        let dy = (to.1 - from.1) as f32 / y_resolution as f32;
+                        return dx.hypot(dy) > mm;
+                    }
+                    _ => fallback_pixels,
+                }
+            }
+        };
+        let from = self.exact_logical_position(from);
+        let to = self.exact_logical_position(to);
+        (to.x - from.x).hypot(to.y - from.y) > pixels
+    }
+
+    /// True if a raw position is outside the configured edge margins
+    fn in_active_area(&self, position: (i32, i32)) -> bool {
+        let Some(margin) = self.config.edge_margin else {
+            return true;
+        };
+        let position = self.unshifted_logical_position(position);
+        let (x_info, y_info) = self.position_axis_info();
+        if let (Some(x_info), Some(y_info)) = (x_info, y_info) {
+            let min = self.unshifted_logical_position((x_info.minimum(), y_info.minimum()));
+            let max = self.unshifted_logical_position((x_info.maximum(), y_info.maximum()));
+            position.x >= min.x + margin.left
+                && position.x <= max.x - margin.right
+                && position.y >= min.y + margin.top
+                && position.y <= max.y - margin.bottom
+        } else {
+            // Without axis ranges only the top and left edges are known
+            position.x >= margin.left && position.y >= margin.top
+        }
+    }
+
+    /// The ranges of the axes providing the X and Y positions, taking swapped axes into account
+    fn position_axis_info(&self) -> (Option<AbsInfo>, Option<AbsInfo>) {
+        if self.config.swap_axes {
+            (self.caps.y_info, self.caps.x_info)
+        } else {
+            (self.caps.x_info, self.caps.y_info)
+        }
+    }
+
+    /// Take the position from the values of the device's position axes, read when it was opened
+    /// or reset
+    pub fn seed_position(&mut self, x: Option<i32>, y: Option<i32>) {
+        if let Some(x) = x {
+            self.set_device_axis(Axis::X, x);
+        }
+        if let Some(y) = y {
+            self.set_device_axis(Axis::Y, y);
+        }
+    }
+
+    /// Store a value from one of the device's position axes
+    ///
+    /// A configured position handler takes the place of the device's position axes.
+    fn set_device_axis(&mut self, axis: Axis, value: i32) {
+        if self.config.position_handler.is_some() {
+            return;
+        }
+        set_axis(&mut self.last_position, axis, value, self.config.swap_axes);
+        match axis {
+            Axis::X => self.axes_seen.0 = true,
+            Axis::Y => self.axes_seen.1 = true,
+        }
+    }
+
+    /// Store a value from one of the stick axes, returning false if `code` is not a stick axis
+    fn push_stick_axis(&mut self, code: AbsoluteAxisCode, value: i32) -> bool {
+        let Some(stick) = self.config.stick_pointer else {
+            return false;
+        };
+        let (min, max) = (stick.range.0 as f32, stick.range.1 as f32);
+        let half_span = (max - min) / 2.0;
+        let deflection = if half_span > 0.0 {
+            ((value as f32 - (min + half_span)) / half_span).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        };
+        if code == stick.x_axis {
+            self.stick.0 = deflection;
+        } else if code == stick.y_axis {
+            self.stick.1 = deflection;
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// The interval at which the pointer is moved, if the stick is deflected beyond its deadzone
+    pub fn stick_tick(&self) -> Option<Duration> {
+        let stick = self.config.stick_pointer?;
+        let moving = stick.velocity(self.stick.0) != 0.0 || stick.velocity(self.stick.1) != 0.0;
+        moving.then_some(stick.tick)
+    }
+
+    /// Move the pointer as driven by the stick over `elapsed`
+    pub fn advance_stick(&mut self, elapsed: Duration) -> Option<WindowEvent> {
+        let stick = self.config.stick_pointer?;
+        let (vx, vy) = (stick.velocity(self.stick.0), stick.velocity(self.stick.1));
+        if vx == 0.0 && vy == 0.0 {
+            return None;
+        }
+        // Continue from the exact position unless touch has since moved the pointer
+        let mut position = match self.stick_position {
+            Some(position) if self.raw_position(position) == self.last_position => position,
+            _ => self.exact_logical_position(self.last_position),
+        };
+        position.x += vx * elapsed.as_secs_f32();
+        position.y += vy * elapsed.as_secs_f32();
+        if let (Some(x_info), Some(y_info)) = self.position_axis_info() {
+            let min = self.exact_logical_position((x_info.minimum(), y_info.minimum()));
+            let max = self.exact_logical_position((x_info.maximum(), y_info.maximum()));
+            position.x = position.x.clamp(min.x.min(max.x), min.x.max(max.x));
+            position.y = position.y.clamp(min.y.min(max.y), min.y.max(max.y));
+        }
+        self.stick_position = Some(position);
+        let raw = self.raw_position(position);
+        if raw == self.last_position && self.position_known() {
+            return None;
+        }
+        self.last_position = raw;
+        self.axes_seen = (true, true);
+        if self.paused.is_some() {
+            return None;
+        }
+        let event = self.moved();
+        if event.is_some() {
+            self.metrics.window_events += 1;
+        }
+        event
+    }
+
+    /// True if the position has been reported, or comes from elsewhere
+    fn position_known(&self) -> bool {
+        self.axes_seen == (true, true) || self.config.position_handler.is_some()
+    }
+
+    fn push_mt_position(&mut self, axis: Axis, value: i32) {
+        if self.in_active_slot() {
+            self.set_device_axis(axis, value);
+        }
+        let swap = self.config.swap_axes;
+        if let Some(slot) = self.current_slot_mut() {
+            set_axis(&mut slot.position, axis, value, swap);
+            match axis {
+                Axis::X => slot.axes_seen.0 = true,
+                Axis::Y => slot.axes_seen.1 = true,
+            }
+            slot.moved = true;
+        }
+    }
+
+    /// The state of the slot which ABS_MT events currently refer to
+    fn current_slot_mut(&mut self) -> Option<&mut Slot> {
+        let index = usize::try_from(self.current_slot)
+            .ok()
+            .filter(|&index| index < MAX_SLOTS)?;
+        if index >= self.slots.len() {
+            self.slots.resize(index + 1, Slot::default());
+        }
+        Some(&mut self.slots[index])
+    }
+
+    /// True if contacts are tracked in slots, either reported by the device or filled from type-A
+    /// reports
+    fn has_slots(&self) -> bool {
+        self.caps.multitouch || self.caps.type_a
+    }
+
+    /// End the type-A contact currently being reported
+    fn finish_type_a_contact(&mut self) {
+        let contact = std::mem::take(&mut self.type_a_contact);
+        // An empty report indicates that there are no contacts
+        if contact != TypeAContact::default() {
+            self.type_a_contacts.push(contact);
+        }
+    }
+
+    /// Update the slots from the type-A contacts reported during the frame
+    ///
+    /// Each contact is placed in the slot matching its position in the report, as if it had been
+    /// reported by a type-B device. Contacts without a tracking ID are identified by that
+    /// position.
+    fn finish_type_a_frame(&mut self) {
+        let contacts = std::mem::take(&mut self.type_a_contacts);
+        let count = contacts.len().max(self.slots.len()).min(MAX_SLOTS);
+        for index in 0..count {
+            self.current_slot = index as i32;
+            let reported_id = self.slots.get(index).map_or(-1, |slot| slot.tracking_id);
+            match contacts.get(index) {
+                Some(contact) => {
+                    let id = contact.tracking_id.unwrap_or(index as i32);
+                    if id != reported_id {
+                        self.push_tracking_id(id);
+                    }
+                    if let Some(x) = contact.x {
+                        self.push_mt_position(Axis::X, x);
+                    }
+                    if let Some(y) = contact.y {
+                        self.push_mt_position(Axis::Y, y);
+                    }
+                    if let (Some(pressure), Some(slot)) =
+                        (contact.pressure, self.current_slot_mut())
+                    {
+                        slot.pressure = Some(pressure);
+                    }
+                }
+                None if reported_id >= 0 => self.push_tracking_id(-1),
+                None => (),
+            }
+        }
+        self.current_slot = 0;
+    }
+
+    /// End the contacts of slots which no event referred to during the frame
+    fn lift_unreported_slots(&mut self) {
+        let current_slot = self.current_slot;
+        for index in 0..self.slots.len() {
+            let slot = &mut self.slots[index];
+            let lifted = slot.tracking_id >= 0 && !slot.reported_in_frame;
+            slot.reported_in_frame = false;
+            if lifted {
+                self.current_slot = index as i32;
+                self.push_tracking_id(-1);
+            }
+        }
+        self.current_slot = current_slot;
+    }
+
+    /// Generate per-contact events for the slots which changed during the frame
+    fn finish_multitouch_frame(&mut self) {
+        // Events not taken by the time the next frame ends are discarded
+        self.multitouch_events.clear();
+        if !self.has_slots() {
+            return;
+        }
+        for index in 0..self.slots.len() {
+            let slot = self.slots[index];
+            // A contact is reported once it is pressed firmly enough, and then until it is lifted
+            let firm = match (self.config.min_contact_pressure, slot.pressure) {
+                (Some(min), Some(pressure)) => pressure >= min,
+                _ => true,
+            };
+            let tracking_id = if slot.reported_id == slot.tracking_id || firm {
+                slot.tracking_id
+            } else {
+                -1
+            };
+            if slot.reported_id != tracking_id {
+                if slot.reported_id >= 0 {
+                    let position = if self.config.release_at_press {
+                        slot.press_position
+                    } else {
+                        slot.position
+                    };
+                    self.multitouch_events.push_back(MultiTouchEvent {
+                        id: slot.reported_id,
+                        event: WindowEvent::PointerReleased {
+                            position: self.logical_position(position),
+                            button: self.config.default_button,
+                        },
+                        pressure: slot.pressure,
+                    });
+                }
+                if tracking_id >= 0 {
+                    self.slots[index].press_position = slot.position;
+                    self.multitouch_events.push_back(MultiTouchEvent {
+                        id: tracking_id,
+                        event: WindowEvent::PointerPressed {
+                            position: self.logical_position(slot.position),
+                            button: self.config.default_button,
+                        },
+                        pressure: slot.pressure,
+                    });
+                }
+            } else if tracking_id >= 0 && slot.moved {
+                self.multitouch_events.push_back(MultiTouchEvent {
+                    id: tracking_id,
+                    event: WindowEvent::PointerMoved {
+                        position: self.logical_position(slot.position),
+                    },
+                    pressure: slot.pressure,
+                });
+            }
+            let slot = &mut self.slots[index];
+            slot.reported_id = tracking_id;
+            slot.moved = false;
+        }
+    }
+
+    /// Generate gesture events for changes in the number of contacts during the frame
+    fn finish_gesture_frame(&mut self) {
+        // Events not taken by the time the next frame ends are discarded
+        self.gesture_events.clear();
+        let count = if self.has_slots() {
+            self.slots
+                .iter()
+                .filter(|slot| slot.tracking_id >= 0)
+                .count()
+        } else {
+            usize::from(self.contact || self.active_slot.is_some())
+        };
+        if count != self.contact_count {
+            self.contact_count = count;
+            self.gesture_events
+                .push_back(GestureEvent::ContactCountChanged { count });
+        }
+        if let Some((row, col)) = self.tapped_cell.take() {
+            self.gesture_events
+                .push_back(GestureEvent::CellTapped { row, col });
+        }
+    }
+
+    /// The tap grid cell containing a raw position, if any
+    fn grid_cell(&self, position: (i32, i32)) -> Option<(usize, usize)> {
+        let grid = self.config.tap_grid?;
+        let position = self.exact_logical_position(position);
+        let cell = |value: f32, size: f32, count: usize| {
+            let index = (value / size * count as f32).floor();
+            (index >= 0.0 && index < count as f32).then_some(index as usize)
+        };
+        Some((
+            cell(position.y, grid.height, grid.rows)?,
+            cell(position.x, grid.width, grid.cols)?,
+        ))
+    }
+
+    /// Track the tool in use from the `BTN_TOOL_*` keys
+    ///
+    /// The tool is kept when it leaves range, so the release of a contact reports the tool which
+    /// made it.
+    fn push_tool_key(&mut self, key: KeyCode, value: i32) {
+        let tool = match key {
+            KeyCode::BTN_TOOL_FINGER => StylusTool::Finger,
+            KeyCode::BTN_TOOL_PEN => StylusTool::Pen,
+            KeyCode::BTN_TOOL_RUBBER => StylusTool::Eraser,
+            _ => return,
+        };
+        if value != 0 {
+            self.tool = tool;
+        }
+    }
+
+    /// Track the modifier keys, returning the key event to report if they are forwarded
+    fn push_modifier_key(&mut self, key: KeyCode, value: i32) -> Option<WindowEvent> {
+        let index = MODIFIER_KEYS.iter().position(|(code, _)| *code == key)?;
+        let bit = 1 << index;
+        let was_down = self.modifier_keys & bit != 0;
+        match value {
+            0 => self.modifier_keys &= !bit,
+            1 => self.modifier_keys |= bit,
+            // Autorepeat
+            _ => return None,
+        }
+        let is_modifier_button = self
+            .config
+            .modifier_button
+            .is_some_and(|(modifier, _)| modifier == key);
+        if !self.config.forward_modifiers
+            || was_down == (value != 0)
+            || self.paused.is_some()
+            || is_modifier_button
+            || self.is_contact_key(key)
+        {
+            return None;
+        }
+        let text = MODIFIER_KEYS[index].1.into();
+        Some(if value != 0 {
+            WindowEvent::KeyPressed { text }
+        } else {
+            WindowEvent::KeyReleased { text }
+        })
+    }
+
+    /// True if `text` is that of the key events reporting the contact, rather than a modifier key
+    pub(crate) fn is_contact_text(&self, text: &SharedString) -> bool {
+        self.config.contact_as_key.as_ref() == Some(text)
+    }
+
+    /// The modifier keys which are down
+    pub fn current_modifiers(&self) -> Modifiers {
+        let held = |mask: u8| self.modifier_keys & mask != 0;
+        Modifiers {
+            shift: held(0b0000_0011),
+            control: held(0b0000_1100),
+            alt: held(0b0011_0000),
+            meta: held(0b1100_0000),
+        }
+    }
+
+    /// The source of contact, taking configured contact keys into account
+    fn contact_source(&self) -> ContactSource {
+        match self.config.contact_keys.first() {
+            Some(&key) => ContactSource::Key(key),
+            None if self.single_touch_from_mt() => ContactSource::TrackingId,
+            None => self.caps.contact_source,
+        }
+    }
+
+    fn is_contact_key(&self, key: KeyCode) -> bool {
+        if self.config.contact_keys.is_empty() {
+            self.contact_source() == ContactSource::Key(key)
+        } else {
+            self.config.contact_keys.contains(&key)
+        }
+    }
+
+    /// Update the contact state from one of the contact keys
+    ///
+    /// Contact is down while any contact key is down, except that `BTN_TOUCH` takes precedence
+    /// over the tool keys on devices which report it. Tool keys report a tool in range, which may
+    /// be hovering, so they then only select the tool.
+    fn push_contact_key(&mut self, key: KeyCode, value: i32) {
+        let down = if self.config.invert_touch_polarity {
+            value == 0
+        } else {
+            value == 1
+        };
+        if down {
+            if !self.contact_keys_down.contains(&key) {
+                self.contact_keys_down.push(key);
+            }
+        } else {
+            self.contact_keys_down.retain(|&down| down != key);
+        }
+        if key == KeyCode::BTN_TOUCH {
+            self.touch_reported = true;
+        }
+        let touch_authoritative = self.is_contact_key(KeyCode::BTN_TOUCH)
+            && (self.touch_reported
+                || self.caps.contact_source == ContactSource::Key(KeyCode::BTN_TOUCH));
+        let contact = self
+            .contact_keys_down
+            .iter()
+            .any(|&key| !(touch_authoritative && is_tool_key(key)));
+        if contact != self.contact {
+            self.contact = contact;
+            self.change_contact(contact);
+        }
+    }
+
+    /// Record a change in contact to be reported at the end of the frame
+    fn change_contact(&mut self, down: bool) {
+        self.button_change = match (down, self.button_change) {
+            (false, ButtonChange::Down) => ButtonChange::Tap,
+            (false, _) => ButtonChange::Up,
+            (true, _) => ButtonChange::Down,
+        };
+    }
+
+    fn push_tracking_id(&mut self, id: i32) {
+        let previous_id = self.current_slot_mut().map(|slot| {
+            if id >= 0 && slot.tracking_id != id {
+                // A new contact has no pressure until it is reported
+                slot.pressure = None;
+            }
+            std::mem::replace(&mut slot.tracking_id, id)
+        });
+        let report_contact = self.contact_source() == ContactSource::TrackingId;
+        if id >= 0
+            && self.active_slot == Some(self.current_slot)
+            && previous_id.is_some_and(|previous| previous >= 0 && previous != id)
+        {
+            // The pointer's slot was reused without its contact being lifted
+            if report_contact {
+                self.change_contact(true);
+            }
+        } else if id >= 0 {
+            // A new contact only becomes the pointer if no other contact is already down
+            if self.active_slot.is_none() {
+                self.active_slot = Some(self.current_slot);
+                if self.single_touch_from_mt()
+                    && self.config.position_handler.is_none()
+                    && let Some(slot) = self.current_slot_mut()
+                {
+                    // Other slots are not followed while no contact is down, so the position
+                    // may have been reported before the tracking id
+                    self.last_position = slot.position;
+                }
+                if report_contact {
+                    self.change_contact(true);
+                }
+            }
+        } else if self.active_slot == Some(self.current_slot) {
+            self.active_slot = None;
+            if report_contact {
+                self.change_contact(false);
+            }
+        }
+    }
+
+    /// True if ABS_MT events currently refer to the slot reported as the pointer
+    ///
+    /// While no contact is down, all slots are followed, unless only the pointer's contact is
+    /// followed as configured by `single_touch_from_mt`.
+    fn in_active_slot(&self) -> bool {
+        match self.active_slot {
+            Some(slot) => slot == self.current_slot,
+            None => !self.single_touch_from_mt(),
+        }
+    }
+
+    /// True if the pointer reports only its own contact from a multitouch device
+    fn single_touch_from_mt(&self) -> bool {
+        self.config.single_touch_from_mt && self.caps.multitouch
+    }
+
+    fn last_logical_position(&self) -> LogicalPosition {
+        self.logical_position(self.last_position)
+    }
+
+    /// Convert a raw device position to a logical position for reporting, rounded and snapped as
+    /// configured
+    fn logical_position(&self, raw: (i32, i32)) -> LogicalPosition {
+        let position = self.exact_logical_position(raw);
+        let round = |value: f32| match self.config.rounding {
+            Rounding::None => value,
+            Rounding::Nearest => value.round(),
+            Rounding::Floor => value.floor(),
+        };
+        let (x, y) = (round(position.x), round(position.y));
+        match self.config.grid {
+            Some((step_x, step_y)) => {
+                LogicalPosition::new((x / step_x).round() * step_x, (y / step_y).round() * step_y)
+            }
+            None => LogicalPosition::new(x, y),
+        }
+    }
+
+    /// The logical position of the pointer, as of the last event pushed
+    pub fn current_position(&self) -> LogicalPosition {
+        self.last_logical_position()
+    }
+
+    /// The raw values of the device's X and Y axes, as of the last event pushed
+    pub fn last_raw_position(&self) -> (i32, i32) {
+        let (x, y) = self.last_position;
+        if self.config.swap_axes {
+            (y, x)
+        } else {
+            (x, y)
+        }
+    }
+
+    /// The logical positions reported for the corners of the device's axis ranges
+    ///
+    /// Corners are in the order of the raw axes: (min, min), (max, min), (max, max), (min, max).
+    pub fn corner_positions(&self) -> Option<[LogicalPosition; 4]> {
+        let (x_info, y_info) = (self.caps.x_info?, self.caps.y_info?);
+        let corner = |x, y| {
+            let mut position = (0, 0);
+            set_axis(&mut position, Axis::X, x, self.config.swap_axes);
+            set_axis(&mut position, Axis::Y, y, self.config.swap_axes);
+            self.logical_position(position)
+        };
+        Some([
+            corner(x_info.minimum(), y_info.minimum()),
+            corner(x_info.maximum(), y_info.minimum()),
+            corner(x_info.maximum(), y_info.maximum()),
+            corner(x_info.minimum(), y_info.maximum()),
+        ])
+    }
+
+    /// Convert a logical position back to the raw axis values which the device would report for it
+    ///
+    /// This is the inverse of the conversion applied to reported positions, other than rounding.
+    pub fn raw_position(&self, position: LogicalPosition) -> (i32, i32) {
+        let (dx, dy) = self.config.offset;
+        let position = LogicalPosition::new(position.x - dx, position.y - dy);
+        let normalized = match self.config.coordinate_mode {
+            CoordinateMode::Normalized { width, height } => match self.position_axis_info() {
+                (Some(x_info), Some(y_info)) => Some((
+                    denormalize(position.x / (width * self.output_scale()), x_info),
+                    denormalize(position.y / (height * self.output_scale()), y_info),
+                )),
+                _ => None,
+            },
+            CoordinateMode::Device => None,
+        };
+        let (x, y) = normalized.unwrap_or(if self.config.physical_output {
+            (position.x, position.y)
+        } else {
+            (
+                position.x * self.config.scale_factor,
+                position.y * self.config.scale_factor,
+            )
+        });
+        let (x, y) = (x.round() as i32, y.round() as i32);
+        if self.config.swap_axes {
+            (y, x)
+        } else {
+            (x, y)
+        }
+    }
+
+    /// Convert a raw device position to a logical position without rounding
+    fn exact_logical_position(&self, raw: (i32, i32)) -> LogicalPosition {
+        let position = self.unshifted_logical_position(raw);
+        let (dx, dy) = self.config.offset;
+        LogicalPosition::new(position.x + dx, position.y + dy)
+    }
+
+    /// Convert a raw device position to a logical position, before the offset is applied
+    fn unshifted_logical_position(&self, (x, y): (i32, i32)) -> LogicalPosition {
+        match self.config.coordinate_mode {
+            CoordinateMode::Normalized { width, height } => {
+                let (x_info, y_info) = self.position_axis_info();
+                if let (Some(x_info), Some(y_info)) = (x_info, y_info) {
+                    return LogicalPosition::new(
+                        normalize(x, x_info) * width * self.output_scale(),
+                        normalize(y, y_info) * height * self.output_scale(),
+                    );
+                }
+            }
+            CoordinateMode::Device => (),
+        }
+        if self.config.physical_output {
+            // Device coordinates are already physical pixels, so are reported exactly
+            return LogicalPosition::new(x as f32, y as f32);
+        }
+        LogicalPosition::from_physical(PhysicalPosition::new(x, y), self.config.scale_factor)
+    }
+
+    /// The scale from logical pixels to reported positions
+    fn output_scale(&self) -> f32 {
+        if self.config.physical_output {
+            self.config.scale_factor
+        } else {
+            1.0
+        }
+    }
+}
+
+/// True if `key` reports a tool in range of the device, rather than contact
+fn is_tool_key(key: KeyCode) -> bool {
+    matches!(
+        key,
+        KeyCode::BTN_TOOL_PEN
+            | KeyCode::BTN_TOOL_RUBBER
+            | KeyCode::BTN_TOOL_BRUSH
+            | KeyCode::BTN_TOOL_PENCIL
+            | KeyCode::BTN_TOOL_AIRBRUSH
+            | KeyCode::BTN_TOOL_FINGER
+            | KeyCode::BTN_TOOL_MOUSE
+            | KeyCode::BTN_TOOL_LENS
+            | KeyCode::BTN_TOOL_DOUBLETAP
+            | KeyCode::BTN_TOOL_TRIPLETAP
+            | KeyCode::BTN_TOOL_QUADTAP
+            | KeyCode::BTN_TOOL_QUINTTAP
+    )
+}
+
+/// Map a value in the range 0.0..=1.0 of an axis to a raw axis value
+fn denormalize(value: f32, info: AbsInfo) -> f32 {
+    let span = info.maximum() as f32 - info.minimum() as f32;
+    info.minimum() as f32 + value * span
+}
+
+/// Store an axis value into a position, swapping X and Y if requested
+fn set_axis(position: &mut (i32, i32), axis: Axis, value: i32, swap: bool) {
+    if (axis == Axis::X) != swap {
+        position.0 = value;
+    } else {
+        position.1 = value;
+    }
+}
+
+/// Map a raw axis value to the range 0.0..=1.0 of the axis
+fn normalize(value: i32, info: AbsInfo) -> f32 {
+    let span = info.maximum() as f32 - info.minimum() as f32;
+    if span <= 0.0 {
+        return 0.0;
+    }
+    (value as f32 - info.minimum() as f32) / span
+}
@@ -0,0 +1,1083 @@
+use std::{path::Path, sync::Arc, time::Duration};
+
+use evdev::{AbsoluteAxisCode, EventSummary, KeyCode, SynchronizationCode};
+use slint::{LogicalPosition, SharedString, platform::PointerEventButton};
+
+use crate::Error;
+
+/// The sysfs file holding the size of the first framebuffer, as `width,height`
+const FRAMEBUFFER_SIZE_PATH: &str = "/sys/class/graphics/fb0/virtual_size";
+
+/// Options controlling how evdev events are converted to slint events
+///
+/// Options are set builder-style, starting from [`Config::new()`] or [`Config::default()`].
+///
+/// # Example
+///
+/// ```
+/// use slint_evdev_input::Config;
+///
+/// let config = Config::new(2.0).emit_idle_moves(false);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub(crate) scale_factor: f32,
+    pub(crate) emit_idle_moves: bool,
+    pub(crate) flush_on_read: bool,
+    pub(crate) frame_end_codes: Vec<SynchronizationCode>,
+    pub(crate) suppress_move_at_press: bool,
+    pub(crate) drag_events: bool,
+    pub(crate) single_touch_from_mt: bool,
+    pub(crate) lift_unreported_slots: bool,
+    pub(crate) coordinate_mode: CoordinateMode,
+    pub(crate) physical_output: bool,
+    pub(crate) rounding: Rounding,
+    pub(crate) grid: Option<(f32, f32)>,
+    pub(crate) offset: (f32, f32),
+    pub(crate) release_at_press: bool,
+    pub(crate) release_drift: Option<Distance>,
+    pub(crate) swap_axes: bool,
+    pub(crate) x_axis: AbsoluteAxisCode,
+    pub(crate) y_axis: AbsoluteAxisCode,
+    pub(crate) coalesce_interval: Option<Duration>,
+    pub(crate) max_move_rate: Option<f32>,
+    pub(crate) event_queue_limit: Option<(usize, OverflowPolicy)>,
+    pub(crate) edge_margin: Option<EdgeMargin>,
+    pub(crate) tap_grid: Option<TapGrid>,
+    pub(crate) edge_scroll: Option<(ScrollEdge, f32)>,
+    pub(crate) scroll_surface: Option<ScrollSurface>,
+    pub(crate) capture_on_press: bool,
+    pub(crate) tap_deadzone: Option<Distance>,
+    pub(crate) min_move_distance: Option<Distance>,
+    pub(crate) ghost_filter: Option<Distance>,
+    pub(crate) smoothing: Option<f32>,
+    pub(crate) hover_distance: Option<i32>,
+    pub(crate) min_contact_pressure: Option<i32>,
+    pub(crate) contact_keys: Vec<KeyCode>,
+    pub(crate) invert_touch_polarity: bool,
+    pub(crate) position_handler: Option<PositionHandler>,
+    pub(crate) default_button: PointerEventButton,
+    pub(crate) modifier_button: Option<(KeyCode, PointerEventButton)>,
+    pub(crate) forward_modifiers: bool,
+    pub(crate) contact_as_key: Option<SharedString>,
+    pub(crate) key_repeat: Option<(Duration, Duration)>,
+    pub(crate) max_press_duration: Option<Duration>,
+    pub(crate) park_position: Option<LogicalPosition>,
+    pub(crate) initial_position: Option<LogicalPosition>,
+    pub(crate) stick_pointer: Option<StickPointer>,
+    pub(crate) allow_any_device: bool,
+}
+
+type PositionFn = dyn Fn(&EventSummary, &mut (i32, i32)) + Send + Sync;
+
+/// A function which updates the raw position from device events
+#[derive(Clone)]
+pub(crate) struct PositionHandler(pub(crate) Arc<PositionFn>);
+
+impl std::fmt::Debug for PositionHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PositionHandler(..)")
+    }
+}
+
+/// A distance on the screen, used for movement thresholds
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Distance {
+    /// A distance in logical pixels
+    Pixels(f32),
+    /// A physical distance in millimeters, converted to device counts using the axis resolution
+    /// reported by the device
+    ///
+    /// This keeps thresholds consistent across panels of different densities.
+    Millimeters {
+        /// The distance in millimeters
+        mm: f32,
+        /// The distance in logical pixels to use instead, if the device does not report its axis
+        /// resolution
+        fallback_pixels: f32,
+    },
+}
+
+/// Widths of the margins along each edge of the screen, in logical pixels
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EdgeMargin {
+    /// Margin along the top edge
+    pub top: f32,
+    /// Margin along the right edge
+    pub right: f32,
+    /// Margin along the bottom edge
+    pub bottom: f32,
+    /// Margin along the left edge
+    pub left: f32,
+}
+
+/// An edge of the screen along which vertical drags scroll
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollEdge {
+    /// The left edge
+    Left,
+    /// The right edge
+    Right,
+}
+
+/// A rectangle of the screen in which dragging scrolls, such as a custom scrollbar widget
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScrollSurface {
+    /// Logical X position of the left edge
+    pub x: f32,
+    /// Logical Y position of the top edge
+    pub y: f32,
+    /// Logical width
+    pub width: f32,
+    /// Logical height
+    pub height: f32,
+}
+
+/// A grid of equally sized cells over the screen, for classifying taps
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TapGrid {
+    /// Number of rows
+    pub rows: usize,
+    /// Number of columns
+    pub cols: usize,
+    /// Logical width of the grid, starting from x = 0
+    pub width: f32,
+    /// Logical height of the grid, starting from y = 0
+    pub height: f32,
+}
+
+/// Which events are dropped when the queue of the async stream exceeds its capacity
+///
+/// Presses, releases, and other events which are not moves are never dropped. See
+/// [`Config::event_queue_limit()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Drop the oldest moves until the queue is within its capacity
+    #[default]
+    DropOldestMoves,
+    /// Drop every move other than the most recent
+    KeepLatestMove,
+}
+
+/// A pair of absolute axes, such as an analog stick or a hat switch, which drive the pointer
+///
+/// The deflection of each axis from the center of its range sets the speed of the pointer along
+/// it. See [`Config::stick_pointer()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StickPointer {
+    /// The axis moving the pointer horizontally
+    pub x_axis: AbsoluteAxisCode,
+    /// The axis moving the pointer vertically
+    pub y_axis: AbsoluteAxisCode,
+    /// The raw minimum and maximum values of both axes, as reported by e.g. `evtest`
+    pub range: (i32, i32),
+    /// The fraction of the range either side of the center within which the axis is at rest
+    pub deadzone: f32,
+    /// The speed of the pointer at full deflection, in logical pixels per second
+    pub speed: f32,
+    /// The exponent of the curve from deflection to speed
+    ///
+    /// 1.0 gives a speed proportional to the deflection, while larger values give finer control
+    /// near the center.
+    pub exponent: f32,
+    /// The interval at which the pointer is moved while the stick is deflected
+    pub tick: Duration,
+}
+
+impl StickPointer {
+    /// Drive the pointer with `x_axis` and `y_axis`, whose values span `range`
+    ///
+    /// Use a range of `(-1, 1)` for a hat switch such as `ABS_HAT0X` and `ABS_HAT0Y`. The other
+    /// parameters start with a deadzone of 0.1, a speed of 400.0 logical pixels per second, an
+    /// exponent of 2.0, and a tick of 16 ms.
+    pub fn new(x_axis: AbsoluteAxisCode, y_axis: AbsoluteAxisCode, range: (i32, i32)) -> Self {
+        Self {
+            x_axis,
+            y_axis,
+            range,
+            deadzone: 0.1,
+            speed: 400.0,
+            exponent: 2.0,
+            tick: Duration::from_millis(16),
+        }
+    }
+
+    /// The velocity of the pointer along an axis, in logical pixels per second, for a deflection
+    /// in the range -1.0..=1.0
+    pub(crate) fn velocity(&self, deflection: f32) -> f32 {
+        let magnitude = deflection.abs();
+        if magnitude <= self.deadzone || self.deadzone >= 1.0 {
+            return 0.0;
+        }
+        let scaled = (magnitude - self.deadzone) / (1.0 - self.deadzone);
+        deflection.signum() * self.speed * scaled.powf(self.exponent)
+    }
+}
+
+/// Selects how raw device coordinates are mapped to logical positions
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CoordinateMode {
+    /// Device coordinates are treated as physical pixels, and converted to logical pixels using
+    /// the scale factor
+    #[default]
+    Device,
+    /// Device coordinates are normalized against the axis minimum and maximum reported by the
+    /// device, then scaled to a logical size of `width` x `height`.
+    ///
+    /// Using a size of 1.0 x 1.0 yields normalized coordinates. If the device does not report
+    /// axis ranges, coordinates are mapped as in [`CoordinateMode::Device`].
+    Normalized {
+        /// Logical width corresponding to the axis maximum
+        width: f32,
+        /// Logical height corresponding to the axis maximum
+        height: f32,
+    },
+}
+
+/// How reported logical positions are rounded
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Rounding {
+    /// Positions are reported exactly
+    #[default]
+    None,
+    /// Positions are rounded to the nearest integer
+    Nearest,
+    /// Positions are rounded down to an integer
+    Floor,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            scale_factor: 1.0,
+            emit_idle_moves: true,
+            flush_on_read: false,
+            frame_end_codes: vec![SynchronizationCode::SYN_REPORT],
+            suppress_move_at_press: false,
+            drag_events: false,
+            single_touch_from_mt: false,
+            lift_unreported_slots: false,
+            coordinate_mode: CoordinateMode::Device,
+            physical_output: false,
+            rounding: Rounding::None,
+            grid: None,
+            offset: (0.0, 0.0),
+            release_at_press: false,
+            release_drift: None,
+            swap_axes: false,
+            x_axis: AbsoluteAxisCode::ABS_X,
+            y_axis: AbsoluteAxisCode::ABS_Y,
+            coalesce_interval: None,
+            max_move_rate: None,
+            event_queue_limit: None,
+            edge_margin: None,
+            tap_grid: None,
+            edge_scroll: None,
+            scroll_surface: None,
+            capture_on_press: false,
+            tap_deadzone: None,
+            min_move_distance: None,
+            ghost_filter: None,
+            smoothing: None,
+            hover_distance: None,
+            min_contact_pressure: None,
+            contact_keys: Vec::new(),
+            invert_touch_polarity: false,
+            position_handler: None,
+            default_button: PointerEventButton::Left,
+            modifier_button: None,
+            forward_modifiers: false,
+            contact_as_key: None,
+            key_repeat: None,
+            max_press_duration: None,
+            park_position: None,
+            initial_position: None,
+            stick_pointer: None,
+            allow_any_device: false,
+        }
+    }
+}
+
+impl Config {
+    /// Create a default config with the given scale factor
+    ///
+    /// `scale_factor` is the scale factor from slint for converting between logical and physical
+    /// coordinates.
+    pub fn new(scale_factor: f32) -> Self {
+        Self {
+            scale_factor,
+            ..Default::default()
+        }
+    }
+
+    /// Set the scale factor for converting between logical and physical coordinates
+    ///
+    /// The scale factor must be positive. Opening a device with any other value, such as the 0.0
+    /// reported by a slint window which has not yet been shown, fails with
+    /// [`Error::InvalidScaleFactor`]. Elsewhere, e.g. when converting events without a device or
+    /// when the config is replaced, it is replaced by 1.0 with a logged warning.
+    pub fn scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    /// Set whether a `PointerMoved` is emitted for a sync which did not change the position
+    ///
+    /// Many drivers report periodic syncs while a finger rests on the screen. When disabled, a
+    /// `PointerMoved` is only emitted if the position differs from the previously emitted move.
+    ///
+    /// Default: `true`
+    pub fn emit_idle_moves(mut self, enable: bool) -> Self {
+        self.emit_idle_moves = enable;
+        self
+    }
+
+    /// Set whether pointer events from a multitouch device report only the first contact
+    ///
+    /// By default, contact follows `BTN_TOUCH` where the device reports it, so the pointer stays
+    /// pressed while any finger is down, and moves to another finger when the first is lifted. When
+    /// enabled, the pointer is pressed by the first contact and released when that contact lifts.
+    /// Other contacts are ignored, and the pointer does not move while no contact is being
+    /// followed. Per-contact events from
+    /// [`fetch_multitouch()`](crate::SlintEventsWrapper::fetch_multitouch) are unaffected.
+    ///
+    /// Default: `false`
+    pub fn single_touch_from_mt(mut self, enable: bool) -> Self {
+        self.single_touch_from_mt = enable;
+        self
+    }
+
+    /// Set whether a multitouch contact is lifted when a frame does not report its slot
+    ///
+    /// This is a workaround for devices which never report an `ABS_MT_TRACKING_ID` of -1, and
+    /// instead signal a lift by leaving the contact's slot out of the next frame, so that e.g. a
+    /// `SYN_REPORT` with no slots at all lifts every contact. A slot counts as reported by any
+    /// `ABS_MT` event for it, including `ABS_MT_SLOT` selecting it. Only enable this for devices
+    /// which report every contact in every frame, since the kernel otherwise leaves out slots
+    /// whose contact has not changed.
+    ///
+    /// Default: `false`
+    pub fn lift_unreported_slots(mut self, enable: bool) -> Self {
+        self.lift_unreported_slots = enable;
+        self
+    }
+
+    /// Set whether moves of a pressed contact are reported as drags from
+    /// [`fetch_extended()`](crate::SlintEventsWrapper::fetch_extended)
+    ///
+    /// When enabled, such moves are reported as [`ExtendedEvent::Drag`](crate::ExtendedEvent::Drag),
+    /// carrying the button of the press which started the drag, so that drag handling need not
+    /// track presses itself. Events from [`fetch_events()`](crate::SlintEventsWrapper::fetch_events)
+    /// are unchanged, as are stylus events, which already carry the pen state.
+    ///
+    /// Default: `false`
+    pub fn drag_events(mut self, enable: bool) -> Self {
+        self.drag_events = enable;
+        self
+    }
+
+    /// Set whether events read without a trailing `SYN_REPORT` are acted on at the end of the read
+    ///
+    /// This is a workaround for drivers which rarely or never report `SYN_REPORT`, so that their
+    /// moves and contact changes are not held indefinitely. When enabled, the end of each read from
+    /// the device is treated as the end of a frame. It applies to
+    /// [`fetch_events()`](crate::SlintEventsWrapper::fetch_events) and the methods built on it,
+    /// to [`EventStream::next_event()`](crate::tokio::EventStream::next_event) and
+    /// [`drain()`](crate::tokio::EventStream::drain), where the end of the input which is ready is
+    /// treated as the end of a read, and to the end of the events given to a
+    /// [`SlintEventsConverter`](crate::SlintEventsConverter). Devices which report
+    /// `SYN_REPORT` correctly are unaffected, since each read ends with one.
+    ///
+    /// Default: `false`
+    pub fn flush_on_read(mut self, enable: bool) -> Self {
+        self.flush_on_read = enable;
+        self
+    }
+
+    /// Set which synchronization codes end a frame
+    ///
+    /// This is for drivers which do not end their frames with `SYN_REPORT`, e.g. a type-A device
+    /// which ends each report with `SYN_MT_REPORT`. Every listed code ends a frame, so with
+    /// `SYN_MT_REPORT` each type-A contact is a frame of its own, which suits only devices that
+    /// report a single contact. The events after `SYN_DROPPED` are discarded up to the next of
+    /// these codes. `SYN_DROPPED` itself is ignored here.
+    ///
+    /// Default: `[SYN_REPORT]`
+    pub fn frame_end_codes(mut self, codes: impl IntoIterator<Item = SynchronizationCode>) -> Self {
+        self.frame_end_codes = codes.into_iter().collect();
+        self
+    }
+
+    /// Set whether moves which report the press position are suppressed until the contact moves
+    ///
+    /// Many drivers follow a press with a sync at the same position, which is otherwise reported
+    /// as a `PointerMoved` immediately after the `PointerPressed`. When enabled, no `PointerMoved`
+    /// is emitted for a contact until its position differs from where it was pressed.
+    ///
+    /// Default: `false`
+    pub fn suppress_move_at_press(mut self, enable: bool) -> Self {
+        self.suppress_move_at_press = enable;
+        self
+    }
+
+    /// Set how raw device coordinates are mapped to logical positions
+    ///
+    /// Default: [`CoordinateMode::Device`]
+    pub fn coordinate_mode(mut self, mode: CoordinateMode) -> Self {
+        self.coordinate_mode = mode;
+        self
+    }
+
+    /// Map the device's axis ranges to the size of the framebuffer, read from
+    /// `/sys/class/graphics/fb0/virtual_size`
+    ///
+    /// This sets a [`CoordinateMode::Normalized`] size of the framebuffer's physical size divided
+    /// by the scale factor, so the scale factor must be set first. Fails with
+    /// [`Error::ScreenSizeUnavailable`] if the size cannot be read, e.g. on systems without a
+    /// framebuffer device, in which case the size must be set with
+    /// [`coordinate_mode()`](Self::coordinate_mode).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slint_evdev_input::{Config, CoordinateMode};
+    ///
+    /// let config = Config::new(1.0);
+    /// let config = config.clone().auto_screen_size().unwrap_or_else(|_| {
+    ///     config.coordinate_mode(CoordinateMode::Normalized {
+    ///         width: 800.0,
+    ///         height: 480.0,
+    ///     })
+    /// });
+    /// ```
+    pub fn auto_screen_size(self) -> Result<Self, Error> {
+        self.screen_size_from(FRAMEBUFFER_SIZE_PATH)
+    }
+
+    /// Map the device's axis ranges to a screen size read from `path`, as
+    /// [`auto_screen_size()`](Self::auto_screen_size) does
+    ///
+    /// The file holds the physical width and height separated by a comma, in the format of
+    /// `/sys/class/graphics/fbN/virtual_size`, e.g. for a framebuffer other than the first.
+    pub fn screen_size_from(mut self, path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let (width, height) = read_screen_size(path)
+            .ok_or_else(|| Error::ScreenSizeUnavailable(path.to_path_buf()))?;
+        let scale_factor = if self.scale_factor.is_finite() && self.scale_factor > 0.0 {
+            self.scale_factor
+        } else {
+            1.0
+        };
+        self.coordinate_mode = CoordinateMode::Normalized {
+            width: width as f32 / scale_factor,
+            height: height as f32 / scale_factor,
+        };
+        Ok(self)
+    }
+
+    /// Set whether positions are reported in physical pixels rather than logical pixels
+    ///
+    /// This is for applications which apply their own scaling, so that positions are not divided
+    /// by the scale factor only to be multiplied by it again. Slint's events only carry logical
+    /// positions, so the physical coordinates are reported in their place. In
+    /// [`CoordinateMode::Device`], device coordinates are reported exactly, and in
+    /// [`CoordinateMode::Normalized`] the logical size is scaled up by the scale factor. Options
+    /// given in logical pixels, such as the [`offset()`](Self::offset) and
+    /// [`edge_margin()`](Self::edge_margin), are then in physical pixels too.
+    ///
+    /// Default: `false`
+    pub fn physical_output(mut self, enable: bool) -> Self {
+        self.physical_output = enable;
+        self
+    }
+
+    /// Set an offset, in logical pixels, which is added to reported positions
+    ///
+    /// This aligns touches with a UI rendered into part of the panel, e.g. when letterboxing. For
+    /// a UI drawn with its origin at `(x, y)` on the panel, use an offset of `(-x, -y)`. The offset
+    /// is applied after the coordinate mode and before rounding, and the
+    /// [`edge_margin()`](Self::edge_margin) still follows the edges of the panel.
+    ///
+    /// Default: `(0.0, 0.0)`
+    pub fn offset(mut self, dx: f32, dy: f32) -> Self {
+        self.offset = (dx, dy);
+        self
+    }
+
+    /// Set how reported logical positions are rounded
+    ///
+    /// Rounding to integers can help hit testing, particularly with a fractional scale factor.
+    /// Thresholds such as the tap deadzone are measured before rounding.
+    ///
+    /// Default: [`Rounding::None`]
+    pub fn rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Snap reported logical positions to a grid, every `step_x` by `step_y` logical pixels
+    ///
+    /// Positions are snapped to the nearest grid point, after all other transforms including
+    /// [`rounding()`](Self::rounding), e.g. for a schematic editor with snapping. Thresholds such as
+    /// the tap deadzone are measured before snapping. Steps which are not positive are ignored.
+    ///
+    /// Default: off
+    pub fn snap_to_grid(mut self, step_x: f32, step_y: f32) -> Self {
+        self.grid = Some((step_x, step_y));
+        self
+    }
+
+    /// Set whether `PointerReleased` reports the position of the press rather than the last
+    /// position
+    ///
+    /// This gives "click where you pressed" behavior, so that a finger drifting while lifting does
+    /// not move the release off the pressed element.
+    ///
+    /// Default: `false`
+    pub fn release_at_press(mut self, enable: bool) -> Self {
+        self.release_at_press = enable;
+        self
+    }
+
+    /// Set a distance beyond which a release is reported at the last reported position rather
+    /// than its own
+    ///
+    /// A fast lift may report a release position which has drifted well away from the contact,
+    /// landing the click on the wrong element. When set, a release further than `distance` from the
+    /// last move, or from the press if the contact has not moved, is reported at that position
+    /// instead. Smaller differences are reported as they are. Has no effect with
+    /// [`release_at_press()`](Self::release_at_press).
+    ///
+    /// Default: `None`
+    pub fn release_drift(mut self, distance: Option<Distance>) -> Self {
+        self.release_drift = distance;
+        self
+    }
+
+    /// Set whether the device's X and Y axes are swapped
+    ///
+    /// When enabled, the device's X axis is read as the Y position and its Y axis as the X
+    /// position, along with their axis ranges. Coordinates are not inverted. This is applied before
+    /// any other transform.
+    ///
+    /// Default: `false`
+    pub fn swap_axes(mut self, enable: bool) -> Self {
+        self.swap_axes = enable;
+        self
+    }
+
+    /// Set the axis which reports the device's X position
+    ///
+    /// For devices which report position on axes other than `ABS_X`, e.g. `ABS_RX`. The axis
+    /// range is read from this axis when the device is opened. The multitouch position axes are
+    /// unaffected.
+    ///
+    /// Default: `ABS_X`
+    pub fn x_axis(mut self, axis: AbsoluteAxisCode) -> Self {
+        self.x_axis = axis;
+        self
+    }
+
+    /// Set the axis which reports the device's Y position
+    ///
+    /// See [`x_axis()`](Self::x_axis).
+    ///
+    /// Default: `ABS_Y`
+    pub fn y_axis(mut self, axis: AbsoluteAxisCode) -> Self {
+        self.y_axis = axis;
+        self
+    }
+
+    /// Set an interval over which consecutive moves are coalesced into the latest one
+    ///
+    /// Only applies to the async [`EventStream`](crate::tokio::EventStream). Presses and releases
+    /// are never dropped or delayed.
+    ///
+    /// Default: `None`
+    pub fn coalesce_interval(mut self, interval: Option<Duration>) -> Self {
+        self.coalesce_interval = interval;
+        self
+    }
+
+    /// Set the maximum rate, in Hz, at which moves are emitted
+    ///
+    /// Only applies to the async [`EventStream`](crate::tokio::EventStream). A move arriving
+    /// sooner than `1 / rate` after the previous one is held until the next allowed instant, and
+    /// replaced by any further moves arriving in the meantime. Presses and releases are never
+    /// delayed; a held move is emitted immediately before them. Rates which are not positive are
+    /// ignored with a logged warning.
+    ///
+    /// Default: `None`
+    pub fn max_move_rate(mut self, rate: Option<f32>) -> Self {
+        self.max_move_rate = rate;
+        self
+    }
+
+    /// Set a limit of `capacity` on the events queued by the async stream, and which events are
+    /// dropped beyond it
+    ///
+    /// Only applies to the async [`EventStream`](crate::tokio::EventStream). With a limit set,
+    /// each read from the device takes all the input which is ready into a queue, from which events
+    /// are then returned in order. When the consumer falls behind, moves are dropped from the queue
+    /// according to `policy`, so that it does not replay a long obsolete drag path, while presses
+    /// and releases are always kept. The queue may therefore exceed `capacity` if it holds more
+    /// presses and releases than that. Dropped moves are counted in
+    /// [`Metrics::moves_coalesced`](crate::Metrics::moves_coalesced). A capacity of 0 is treated as
+    /// 1.
+    ///
+    /// Default: no limit, and input is read only as events are requested
+    pub fn event_queue_limit(mut self, capacity: usize, policy: OverflowPolicy) -> Self {
+        self.event_queue_limit = Some((capacity, policy));
+        self
+    }
+
+    /// Set margins along each edge of the screen, in logical pixels, within which touches are
+    /// ignored
+    ///
+    /// This rejects false touches where a bezel presses on the edge of the panel. No press, move,
+    /// or release is reported for a touch within the margins. A touch starting in a margin is
+    /// reported as pressed once it moves into the active area, and a pressed touch moving into a
+    /// margin is still reported when it is released.
+    ///
+    /// The right and bottom edges are found from the axis ranges reported by the device.
+    ///
+    /// Default: no margins
+    pub fn edge_margin(mut self, top: f32, right: f32, bottom: f32, left: f32) -> Self {
+        self.edge_margin = Some(EdgeMargin {
+            top,
+            right,
+            bottom,
+            left,
+        });
+        self
+    }
+
+    /// Set a grid of `rows` x `cols` cells, covering `width` x `height` logical pixels, within which
+    /// taps are classified
+    ///
+    /// When a contact is released in the same cell it was pressed in,
+    /// [`GestureEvent::CellTapped`](crate::GestureEvent::CellTapped) is reported from
+    /// [`fetch_extended()`](crate::SlintEventsWrapper::fetch_extended), in addition to the pointer
+    /// events. Taps outside the grid, or ending in a different cell, are not reported.
+    ///
+    /// Default: no grid
+    pub fn tap_grid(mut self, rows: usize, cols: usize, width: f32, height: f32) -> Self {
+        self.tap_grid = Some(TapGrid {
+            rows,
+            cols,
+            width,
+            height,
+        });
+        self
+    }
+
+    /// Set a strip along one edge of the screen, `width` logical pixels wide, in which dragging
+    /// scrolls
+    ///
+    /// A contact pressed within the strip reports no press or release. Instead, each move is
+    /// reported as a `PointerScrolled` with a vertical delta equal to the distance moved, so
+    /// content follows the finger, until the contact is released. Contacts pressed outside the
+    /// strip are reported as usual. The right edge is found from the axis ranges reported by the
+    /// device, so no strip applies on the right of devices which do not report them.
+    ///
+    /// Default: no strip
+    pub fn edge_scroll(mut self, edge: ScrollEdge, width: f32) -> Self {
+        self.edge_scroll = Some((edge, width));
+        self
+    }
+
+    /// Set a rectangle of `width` x `height` logical pixels, with its top left corner at `(x, y)`,
+    /// in which dragging scrolls
+    ///
+    /// This is like [`edge_scroll()`](Self::edge_scroll) for a rectangle anywhere on the screen,
+    /// such as a scrollbar drawn by the UI. A contact pressed within the rectangle reports no
+    /// press or release, and each move is reported as a `PointerScrolled` with deltas equal to the
+    /// distance moved along each axis, until the contact is released. The rectangle is in the
+    /// same coordinates as reported positions, so it follows the [`offset()`](Self::offset).
+    ///
+    /// Default: no surface
+    pub fn scroll_surface(mut self, x: f32, y: f32, width: f32, height: f32) -> Self {
+        self.scroll_surface = Some(ScrollSurface {
+            x,
+            y,
+            width,
+            height,
+        });
+        self
+    }
+
+    /// Set whether a pressed contact keeps reporting moves within the edge margins
+    ///
+    /// When enabled, the [`edge_margin()`](Self::edge_margin) only applies to where a contact is
+    /// pressed. Once pressed, the contact is captured, and moves into the margins are reported
+    /// until it is released, as for a drag.
+    ///
+    /// Default: `false`
+    pub fn capture_on_press(mut self, enable: bool) -> Self {
+        self.capture_on_press = enable;
+        self
+    }
+
+    /// Set a distance which a contact must move from where it was pressed before moves are
+    /// reported
+    ///
+    /// This keeps a tap from being reported as a small drag. Once the contact has left the
+    /// deadzone, all moves are reported until it is released.
+    ///
+    /// Default: `None`
+    pub fn tap_deadzone(mut self, distance: Option<Distance>) -> Self {
+        self.tap_deadzone = distance;
+        self
+    }
+
+    /// Set the `ABS_DISTANCE` above which a hovering pen is considered out of range
+    ///
+    /// Digitizers may report the height of the pen above the surface on `ABS_DISTANCE`, in device
+    /// units. When set, moves are not reported while no contact is pressed and the last reported
+    /// distance exceeds `max_distance`. Devices which do not report the axis are unaffected.
+    ///
+    /// Default: `None`
+    pub fn hover_distance(mut self, max_distance: Option<i32>) -> Self {
+        self.hover_distance = max_distance;
+        self
+    }
+
+    /// Set a distance which a pressed contact must move from the last reported move before the
+    /// next move is reported
+    ///
+    /// This reduces the number of moves during slow drags. Unlike the
+    /// [`tap_deadzone()`](Self::tap_deadzone), it applies throughout the contact: each move is
+    /// measured from the previous one reported, or from the press. Moves while no contact is
+    /// pressed are unaffected.
+    ///
+    /// Default: `None`
+    pub fn min_move_distance(mut self, distance: Option<Distance>) -> Self {
+        self.min_move_distance = distance;
+        self
+    }
+
+    /// Set the `ABS_MT_PRESSURE` which a contact must reach before it is reported as pressed by
+    /// [`fetch_multitouch()`](crate::SlintEventsWrapper::fetch_multitouch)
+    ///
+    /// This rejects light accidental contacts, while firmer contacts register as usual. A contact
+    /// is pressed once its pressure reaches `min_pressure`, and then stays pressed until it is
+    /// lifted. Contacts on devices which do not report the axis are unaffected, as are the events
+    /// of the single pointer.
+    ///
+    /// Default: `None`
+    pub fn min_contact_pressure(mut self, min_pressure: Option<i32>) -> Self {
+        self.min_contact_pressure = min_pressure;
+        self
+    }
+
+    /// Set a distance beyond which a single sample is treated as a possible outlier
+    ///
+    /// Resistive panels may report one wild position between two valid ones. When set, a move
+    /// which jumps further than `distance` from the previous position is held for one frame. If
+    /// the next sample returns within `distance` of the previous position the outlier is dropped,
+    /// otherwise the jump is accepted and the pointer moves to the new sample. An outlier held when
+    /// the contact is released is dropped. Only the pointer events are filtered.
+    ///
+    /// Default: `None`
+    pub fn ghost_filter(mut self, distance: Option<Distance>) -> Self {
+        self.ghost_filter = distance;
+        self
+    }
+
+    /// Set the weight `alpha` of each new sample in an exponential moving average of the position
+    ///
+    /// This smooths the jitter of noisy panels, such as resistive panels reporting a still finger
+    /// at slightly different positions. Each frame, the reported position moves by `alpha` of the
+    /// way from the previous reported position to the device position, so smaller values smooth
+    /// more but lag further behind the finger. The average restarts at the position of each
+    /// press, so strokes start without lag. Values outside `0.0 < alpha <= 1.0` are ignored with a
+    /// logged warning.
+    ///
+    /// Default: `None`
+    pub fn smoothing(mut self, alpha: Option<f32>) -> Self {
+        self.smoothing = alpha;
+        self
+    }
+
+    /// Set the keys which indicate contact
+    ///
+    /// Contact is down while any of the keys is down, so e.g. passing both `BTN_TOUCH` and
+    /// `BTN_TOOL_FINGER` accepts either. Tool keys such as `BTN_TOOL_FINGER` and `BTN_TOOL_PEN`
+    /// report a tool in range, which may be hovering, so when `BTN_TOUCH` is one of the keys and
+    /// the device reports it, it is authoritative: the tool keys are then ignored for contact, and
+    /// only select the tool. If no keys are set, the contact source is detected from the device:
+    /// `BTN_TOUCH` if supported, then `ABS_MT_TRACKING_ID`, then `BTN_TOOL_FINGER`.
+    ///
+    /// Default: detected
+    pub fn contact_keys(mut self, keys: impl IntoIterator<Item = KeyCode>) -> Self {
+        self.contact_keys = keys.into_iter().collect();
+        self
+    }
+
+    /// Set whether the contact keys report contact with a value of 0 rather than 1
+    ///
+    /// Some controllers invert the polarity of `BTN_TOUCH`, reporting 0 while a finger is down.
+    /// When enabled, a contact key value of 0 is treated as down and any other value as up.
+    ///
+    /// Default: `false`
+    pub fn invert_touch_polarity(mut self, enable: bool) -> Self {
+        self.invert_touch_polarity = enable;
+        self
+    }
+
+    /// Set the button reported by presses, releases, and drags of the device's contacts
+    ///
+    /// This makes the whole device act as one button, e.g. a touch strip which always produces
+    /// right clicks. It applies to per-contact multitouch events too.
+    ///
+    /// Default: [`PointerEventButton::Left`]
+    pub fn default_button(mut self, button: PointerEventButton) -> Self {
+        self.default_button = button;
+        self
+    }
+
+    /// Set a key which, when held as a contact is pressed, reports the contact as `button` rather
+    /// than the [`default_button()`](Self::default_button)
+    ///
+    /// This allows e.g. a hardware button held while touching to produce a right click. The button
+    /// is chosen when the contact is pressed, and is used until it is released. Per-contact
+    /// multitouch events always report the default button.
+    ///
+    /// Default: none
+    pub fn modifier_button(mut self, key: KeyCode, button: PointerEventButton) -> Self {
+        self.modifier_button = Some((key, button));
+        self
+    }
+
+    /// Set whether the device's modifier keys are reported as key events
+    ///
+    /// Slint's pointer events do not carry modifiers, but slint applies the modifiers of the keys
+    /// it has seen pressed, so this allows e.g. shift-click with a shift key on the touch device.
+    /// When enabled, each press and release of `KEY_LEFTSHIFT`, `KEY_RIGHTSHIFT`, `KEY_LEFTCTRL`,
+    /// `KEY_RIGHTCTRL`, `KEY_LEFTALT`, `KEY_RIGHTALT`, `KEY_LEFTMETA`, or `KEY_RIGHTMETA` produces a
+    /// `KeyPressed` or `KeyReleased` of the corresponding slint modifier key, as soon as it is read.
+    /// Keys used as contact keys or by [`modifier_button()`](Self::modifier_button) are not
+    /// reported. The held modifiers are available from
+    /// [`SlintEventsWrapper::current_modifiers()`](crate::SlintEventsWrapper::current_modifiers)
+    /// either way.
+    ///
+    /// Default: `false`
+    pub fn forward_modifiers(mut self, enable: bool) -> Self {
+        self.forward_modifiers = enable;
+        self
+    }
+
+    /// Set a position to which the pointer is moved before any events are read
+    ///
+    /// When set, a `PointerMoved` to `position` is the first event returned, without waiting for
+    /// the device. Using a position outside the window clears slint's hover state, so no element
+    /// appears hovered until the screen is first touched. The move is returned again after
+    /// [`reset_state()`](crate::SlintEventsWrapper::reset_state).
+    ///
+    /// Default: `None`
+    pub fn park_position(mut self, position: Option<LogicalPosition>) -> Self {
+        self.park_position = position;
+        self
+    }
+
+    /// Set the position assumed before the device has reported one
+    ///
+    /// Pointer events are otherwise not reported until the device has reported both an X and a Y
+    /// position, since (0, 0) may be a real position. With an initial position, events such as
+    /// a sync or a press without coordinates are reported at this position instead. A device's
+    /// current position is read when it is opened, and again by
+    /// [`reset_state()`](crate::SlintEventsWrapper::reset_state), and takes the place of the
+    /// initial position. The position is converted to the nearest raw device position, so it may
+    /// be reported slightly differently.
+    ///
+    /// Default: `None`
+    pub fn initial_position(mut self, position: Option<LogicalPosition>) -> Self {
+        self.initial_position = position;
+        self
+    }
+
+    /// Set a pair of axes, such as an analog stick, which move the pointer
+    ///
+    /// While the stick is deflected beyond its deadzone, the pointer moves at a speed set by the
+    /// deflection, and a `PointerMoved` is reported every [`tick`](StickPointer::tick). The stick
+    /// moves the same position as touch, but is otherwise independent of it, so e.g. a button
+    /// can be made to click at the pointer with [`contact_keys()`](Self::contact_keys). The
+    /// pointer stops at the edges of the device's position axis ranges, if it reports them.
+    ///
+    /// The pointer is moved by an [`EventStream`](crate::tokio::EventStream), read with
+    /// `next_event()` or `ready()` and `drain()`, so this requires the `tokio` feature, and has no
+    /// effect on blocking reads. Events from other sources can drive the pointer with
+    /// [`EventCollector::advance_stick()`](crate::EventCollector::advance_stick).
+    ///
+    /// When set, opening a device requires only the stick axes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use evdev::AbsoluteAxisCode;
+    /// use slint_evdev_input::{Config, StickPointer};
+    ///
+    /// let stick = StickPointer::new(AbsoluteAxisCode::ABS_HAT0X, AbsoluteAxisCode::ABS_HAT0Y, (-1, 1));
+    /// let config = Config::new(1.0).stick_pointer(Some(stick));
+    /// ```
+    ///
+    /// Default: `None`
+    pub fn stick_pointer(mut self, stick: Option<StickPointer>) -> Self {
+        self.stick_pointer = stick;
+        self
+    }
+
+    /// Set a key which is reported in place of pointer events
+    ///
+    /// When set, a contact going down is reported as `KeyPressed` and going up as `KeyReleased`,
+    /// each with `text`, wherever on the screen the contact is. No pointer events are reported, so
+    /// e.g. a tap anywhere can act as the Enter key of a single button appliance. Per-contact
+    /// multitouch events are unaffected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use slint::platform::Key;
+    /// use slint_evdev_input::Config;
+    ///
+    /// let config = Config::new(1.0).contact_as_key(Some(Key::Return.into()));
+    /// ```
+    ///
+    /// Default: `None`
+    pub fn contact_as_key(mut self, text: Option<SharedString>) -> Self {
+        self.contact_as_key = text;
+        self
+    }
+
+    /// Set how a held contact reported as a key is repeated, like keyboard autorepeat
+    ///
+    /// When set along with [`contact_as_key()`](Self::contact_as_key), a contact held for `delay`
+    /// after its `KeyPressed` is followed by a `KeyPressRepeated`, and then another every
+    /// `interval` until it is released. Repeats are timed by an
+    /// [`EventStream`](crate::tokio::EventStream), read with `next_event()` or `ready()` and
+    /// `drain()`, so this requires the `tokio` feature, and has no effect on blocking reads.
+    ///
+    /// Default: `None`
+    pub fn key_repeat(mut self, repeat: Option<(Duration, Duration)>) -> Self {
+        self.key_repeat = repeat;
+        self
+    }
+
+    /// Set how long a contact may be held without moving before it is released regardless
+    ///
+    /// A faulty controller may leave `BTN_TOUCH` stuck down, leaving the UI pressed forever. When
+    /// set, a contact which is held for `duration` without moving is released, with a warning
+    /// logged, although the device still reports it. Any move restarts the timer, so long drags
+    /// are not interrupted. Timed by an [`EventStream`](crate::tokio::EventStream), read with
+    /// `next_event()` or `ready()` and `drain()`, so this requires the `tokio` feature, and has no
+    /// effect on blocking reads.
+    ///
+    /// Default: `None`
+    pub fn max_press_duration(mut self, duration: Option<Duration>) -> Self {
+        self.max_press_duration = duration;
+        self
+    }
+
+    /// Set whether devices which do not look like a touchscreen are accepted
+    ///
+    /// By default, opening a device fails with [`Error::MissingAxis`](crate::Error::MissingAxis)
+    /// if it does not report X and Y positions, or with
+    /// [`Error::MissingKey`](crate::Error::MissingKey) if it has no way of reporting contact, so
+    /// that e.g. opening a keyboard by mistake is caught early. Positions are not required when a
+    /// [`position_handler()`](Self::position_handler) is set.
+    ///
+    /// Default: `false`
+    pub fn allow_any_device(mut self, enable: bool) -> Self {
+        self.allow_any_device = enable;
+        self
+    }
+
+    /// Set a function which extracts the position from device events, for devices which report
+    /// position other than on a pair of axes
+    ///
+    /// For position reported directly on other axes, [`x_axis()`](Self::x_axis) and
+    /// [`y_axis()`](Self::y_axis) are simpler. The function is called with every event, and
+    /// updates the raw `(x, y)` device position in place. It replaces the built-in handling of the
+    /// position axes and the multitouch position of the pointer contact, so
+    /// [`swap_axes()`](Self::swap_axes) does not apply to the position it sets. Per-contact
+    /// multitouch events are unaffected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use evdev::{AbsoluteAxisCode, EventSummary};
+    /// use slint_evdev_input::Config;
+    ///
+    /// let config = Config::new(1.0).position_handler(|event, position| {
+    ///     if let EventSummary::AbsoluteAxis(_, AbsoluteAxisCode::ABS_MT_POSITION_X, value) = event {
+    ///         position.0 = *value;
+    ///     }
+    ///     if let EventSummary::AbsoluteAxis(_, AbsoluteAxisCode::ABS_Z, value) = event {
+    ///         position.1 = *value / 2;
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// Default: `ABS_X` and `ABS_Y`
+    pub fn position_handler(
+        mut self,
+        handler: impl Fn(&EventSummary, &mut (i32, i32)) + Send + Sync + 'static,
+    ) -> Self {
+        self.position_handler = Some(PositionHandler(Arc::new(handler)));
+        self
+    }
+
+    /// Check the settings which are rejected when opening a device rather than corrected
+    pub(crate) fn check(&self) -> Result<(), Error> {
+        if !self.has_valid_scale_factor() {
+            return Err(Error::InvalidScaleFactor(self.scale_factor));
+        }
+        Ok(())
+    }
+
+    /// True if the scale factor is finite and positive
+    fn has_valid_scale_factor(&self) -> bool {
+        self.scale_factor.is_finite() && self.scale_factor > 0.0
+    }
+
+    /// Replace invalid values with their defaults, logging a warning for each
+    pub(crate) fn validated(mut self) -> Self {
+        if !self.has_valid_scale_factor() {
+            log::warn!(
+                "Invalid scale factor {}, using 1.0 instead",
+                self.scale_factor
+            );
+            self.scale_factor = 1.0;
+        }
+        if let Some(rate) = self.max_move_rate
+            && !(rate.is_finite() && rate > 0.0)
+        {
+            log::warn!("Invalid maximum move rate {rate}, ignoring");
+            self.max_move_rate = None;
+        }
+        if let Some(alpha) = self.smoothing
+            && !(alpha > 0.0 && alpha <= 1.0)
+        {
+            log::warn!("Invalid smoothing factor {alpha}, ignoring");
+            self.smoothing = None;
+        }
+        if let Some((step_x, step_y)) = self.grid
+            && !(step_x.is_finite() && step_x > 0.0 && step_y.is_finite() && step_y > 0.0)
+        {
+            log::warn!("Invalid grid step ({step_x}, {step_y}), ignoring");
+            self.grid = None;
+        }
+        if let Some((capacity, _)) = &mut self.event_queue_limit {
+            *capacity = (*capacity).max(1);
+        }
+        self
+    }
+}
+
+/// Read a screen size in the format `width,height`
+fn read_screen_size(path: &Path) -> Option<(u32, u32)> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    let (width, height) = contents.trim().split_once(',')?;
+    let size = (width.trim().parse().ok()?, height.trim().parse().ok()?);
+    (size.0 > 0 && size.1 > 0).then_some(size)
+}
@@ -0,0 +1,277 @@
+use std::time::Duration;
+
+use evdev::{EventSummary, InputEvent};
+use slint::{LogicalPosition, platform::WindowEvent};
+
+use crate::{Capabilities, Collector, Config, ExtendedEvent, Metrics, Modifiers, MultiTouchEvent};
+
+/// An iterator converting a sequence of evdev events into window events, without a device
+///
+/// This runs the same conversion as [`SlintEventsWrapper`](crate::SlintEventsWrapper), and is
+/// useful for testing with hand-built events.
+///
+/// # Example
+///
+/// ```
+/// use evdev::{AbsoluteAxisCode, EventType, InputEvent, KeyCode, SynchronizationCode};
+/// use slint_evdev_input::{Config, SlintEventsConverter};
+///
+/// let events = [
+///     InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+///     InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+///     InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+///     InputEvent::new(EventType::SYNCHRONIZATION.0, SynchronizationCode::SYN_REPORT.0, 0),
+/// ];
+/// let window_events: Vec<_> = SlintEventsConverter::new(events, Config::default()).collect();
+/// assert_eq!(1, window_events.len());
+/// ```
+pub struct SlintEventsConverter<I> {
+    inner: I,
+    collector: Collector,
+}
+
+impl<I: Iterator<Item = InputEvent>> SlintEventsConverter<I> {
+    /// Create a converter for the given events, as from a single touch device
+    pub fn new(events: impl IntoIterator<IntoIter = I>, config: Config) -> Self {
+        Self::with_capabilities(events, config, Capabilities::default())
+    }
+
+    /// Create a converter for the given events, as from a device with the given capabilities
+    pub fn with_capabilities(
+        events: impl IntoIterator<IntoIter = I>,
+        config: Config,
+        caps: Capabilities,
+    ) -> Self {
+        Self {
+            inner: events.into_iter(),
+            collector: Collector::new(config, caps),
+        }
+    }
+
+    /// Where touches at the corners of the axis ranges would be reported
+    ///
+    /// See [`SlintEventsWrapper::calibration_preview()`](crate::SlintEventsWrapper::calibration_preview).
+    pub fn calibration_preview(&self) -> Option<[LogicalPosition; 4]> {
+        self.collector.corner_positions()
+    }
+
+    /// Counts of the events converted so far
+    pub fn metrics(&self) -> Metrics {
+        self.collector.metrics()
+    }
+
+    /// Convert into an iterator of per-contact events, as from
+    /// [`fetch_multitouch()`](crate::SlintEventsWrapper::fetch_multitouch)
+    pub fn into_multitouch(mut self) -> impl Iterator<Item = MultiTouchEvent> {
+        std::iter::from_fn(move || self.collector.next_multitouch_from(&mut self.inner))
+    }
+
+    /// Convert into an iterator of frames, each the raw events read up to the end of the frame and
+    /// the window event they produced, if any
+    ///
+    /// This shows why particular input did or did not produce a window event. An event produced
+    /// without further input, e.g. a release queued behind a press, is returned with no raw
+    /// events.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use evdev::{AbsoluteAxisCode, EventType, InputEvent, KeyCode, SynchronizationCode};
+    /// use slint_evdev_input::{Config, SlintEventsConverter};
+    ///
+    /// let syn = InputEvent::new(EventType::SYNCHRONIZATION.0, SynchronizationCode::SYN_REPORT.0, 0);
+    /// let events = [
+    ///     InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ///     syn,
+    ///     InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+    ///     InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+    ///     syn,
+    /// ];
+    /// for (raw, window_event) in SlintEventsConverter::new(events, Config::default()).into_frames() {
+    ///     println!("{raw:?} -> {window_event:?}");
+    /// }
+    /// ```
+    pub fn into_frames(mut self) -> impl Iterator<Item = (Vec<EventSummary>, Option<WindowEvent>)> {
+        std::iter::from_fn(move || self.collector.next_frame_from(&mut self.inner))
+    }
+
+    /// Convert into an iterator of extended events, as from
+    /// [`fetch_extended()`](crate::SlintEventsWrapper::fetch_extended)
+    pub fn into_extended(mut self) -> impl Iterator<Item = ExtendedEvent> {
+        std::iter::from_fn(move || self.collector.next_extended_from(&mut self.inner))
+    }
+}
+
+impl<I: Iterator<Item = InputEvent>> Iterator for SlintEventsConverter<I> {
+    type Item = WindowEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The end of the events is treated as the end of a read from a device
+        self.collector
+            .next_from(&mut self.inner)
+            .or_else(|| self.collector.flush_read())
+    }
+}
+
+/// The conversion from evdev events to window events, driven one event at a time
+///
+/// This is the state machine used by [`SlintEventsWrapper`](crate::SlintEventsWrapper), for
+/// integrations which receive evdev events from their own source, e.g. over a network.
+///
+/// # Example
+///
+/// ```
+/// use evdev::{AbsoluteAxisCode, EventType, InputEvent, KeyCode, SynchronizationCode};
+/// use slint_evdev_input::{Config, EventCollector};
+///
+/// let mut collector = EventCollector::new(Config::default());
+/// let mut window_events = Vec::new();
+/// for event in [
+///     InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+///     InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+///     InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+///     InputEvent::new(EventType::SYNCHRONIZATION.0, SynchronizationCode::SYN_REPORT.0, 0),
+/// ] {
+///     window_events.extend(std::iter::from_fn(|| collector.take_queued()));
+///     window_events.extend(collector.push(event));
+/// }
+/// window_events.extend(std::iter::from_fn(|| collector.take_queued()));
+/// assert_eq!(1, window_events.len());
+/// ```
+pub struct EventCollector {
+    collector: Collector,
+}
+
+impl EventCollector {
+    /// Create a collector for events as from a single touch device
+    pub fn new(config: Config) -> Self {
+        Self::with_capabilities(config, Capabilities::default())
+    }
+
+    /// Create a collector for events as from a device with the given capabilities
+    pub fn with_capabilities(config: Config, caps: Capabilities) -> Self {
+        Self {
+            collector: Collector::new(config, caps),
+        }
+    }
+
+    /// Push one evdev event, returning the window event it completes, if any
+    ///
+    /// Window events are normally completed by `SYN_REPORT`. Take every event from
+    /// [`take_queued()`](Self::take_queued) before each push, since some events, such as the
+    /// release of a tap, are generated ahead of the events which would report them.
+    pub fn push(&mut self, event: InputEvent) -> Option<WindowEvent> {
+        self.collector.push(event.destructure())
+    }
+
+    /// Take the next event generated ahead of the pushed events, if any
+    ///
+    /// More than one event may be queued, so call this until it returns `None`.
+    pub fn take_queued(&mut self) -> Option<WindowEvent> {
+        self.collector.take_queued()
+    }
+
+    /// End a frame left open without `SYN_REPORT`, if
+    /// [`Config::flush_on_read()`](crate::Config::flush_on_read) is enabled
+    ///
+    /// Call this at the end of each batch of events received together.
+    pub fn flush(&mut self) -> Option<WindowEvent> {
+        self.collector.flush_read()
+    }
+
+    /// Move the pointer as driven by the [`stick_pointer()`](crate::Config::stick_pointer) over
+    /// `elapsed`, returning the resulting move, if any
+    ///
+    /// Call this every [`StickPointer::tick`](crate::StickPointer::tick) while
+    /// [`stick_tick()`](Self::stick_tick) returns `Some`, with the time since the previous call.
+    pub fn advance_stick(&mut self, elapsed: Duration) -> Option<WindowEvent> {
+        self.collector.advance_stick(elapsed)
+    }
+
+    /// The interval at which to call [`advance_stick()`](Self::advance_stick), if the stick is
+    /// deflected beyond its deadzone
+    pub fn stick_tick(&self) -> Option<Duration> {
+        self.collector.stick_tick()
+    }
+
+    /// The logical position of the pointer, as of the last event pushed
+    ///
+    /// See [`SlintEventsWrapper::current_position()`](crate::SlintEventsWrapper::current_position).
+    pub fn current_position(&self) -> LogicalPosition {
+        self.collector.current_position()
+    }
+
+    /// The raw values of the device's X and Y axes, as of the last event pushed
+    ///
+    /// See [`SlintEventsWrapper::last_raw_position()`](crate::SlintEventsWrapper::last_raw_position).
+    pub fn last_raw_position(&self) -> (i32, i32) {
+        self.collector.last_raw_position()
+    }
+
+    /// The modifier keys held on the device, as of the last event pushed
+    ///
+    /// See [`SlintEventsWrapper::current_modifiers()`](crate::SlintEventsWrapper::current_modifiers).
+    pub fn current_modifiers(&self) -> Modifiers {
+        self.collector.current_modifiers()
+    }
+
+    /// The configuration used to convert events
+    pub fn config(&self) -> &Config {
+        self.collector.config()
+    }
+
+    /// Replace the configuration, keeping the tracked position and contact state
+    ///
+    /// If events of a frame have been pushed without its `SYN_REPORT`, the configuration applies
+    /// once the frame ends.
+    pub fn set_config(&mut self, config: Config) {
+        self.collector.set_config(config);
+    }
+
+    /// Reset the tracked position and contact state
+    ///
+    /// See [`SlintEventsWrapper::reset_state()`](crate::SlintEventsWrapper::reset_state).
+    pub fn reset_state(&mut self) {
+        self.collector.reset();
+    }
+
+    /// An event re-asserting the current contact, if it is pressed
+    ///
+    /// See [`SlintEventsWrapper::replay_state()`](crate::SlintEventsWrapper::replay_state).
+    pub fn replay_state(&self) -> Option<WindowEvent> {
+        self.collector.replay_state()
+    }
+
+    /// Stop producing window events, while still tracking the position and contact
+    ///
+    /// See [`SlintEventsWrapper::pause()`](crate::SlintEventsWrapper::pause).
+    pub fn pause(&mut self) {
+        self.collector.pause(std::iter::empty());
+    }
+
+    /// Produce window events again, returning the event which brings the window up to date
+    ///
+    /// See [`SlintEventsWrapper::resume()`](crate::SlintEventsWrapper::resume).
+    pub fn resume(&mut self) -> Option<WindowEvent> {
+        self.collector.resume()
+    }
+
+    /// True while paused by [`pause()`](Self::pause)
+    pub fn is_paused(&self) -> bool {
+        self.collector.is_paused()
+    }
+
+    /// Counts of the events converted so far
+    pub fn metrics(&self) -> Metrics {
+        self.collector.metrics()
+    }
+}
+
+/// Convert a recorded sequence of evdev events into window events
+///
+/// This is useful for replaying events captured from a device, e.g. with `evtest`, to reproduce
+/// the conversion offline. Events are converted as from a single touch device; use
+/// [`SlintEventsConverter::with_capabilities()`] to replay events from other devices.
+pub fn convert_events(events: &[InputEvent], config: Config) -> Vec<WindowEvent> {
+    SlintEventsConverter::new(events.iter().copied(), config).collect()
+}
@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+use evdev::{AbsoluteAxisCode, KeyCode};
+
+use crate::Error;
+
+/// A description of an input device, for choosing which device to open
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeviceInfo {
+    /// The event device node, e.g. `/dev/input/event0`
+    pub path: PathBuf,
+    /// The name reported by the device, if any
+    pub name: Option<String>,
+    /// True if the device reports touch contact, via `BTN_TOUCH` or multitouch positions
+    pub has_touch: bool,
+    /// True if the device reports absolute X and Y positions
+    pub has_abs_xy: bool,
+    /// True if the device reports relative motion, as a mouse does
+    pub has_rel: bool,
+    /// True if the device has the keys of a keyboard
+    pub is_keyboard: bool,
+}
+
+impl DeviceInfo {
+    /// Describe an open device found at `path`
+    pub fn from_device(path: impl AsRef<Path>, device: &evdev::Device) -> Self {
+        let has_axis = |axis| {
+            device
+                .supported_absolute_axes()
+                .is_some_and(|axes| axes.contains(axis))
+        };
+        let has_key = |key| {
+            device
+                .supported_keys()
+                .is_some_and(|keys| keys.contains(key))
+        };
+        Self {
+            path: path.as_ref().to_path_buf(),
+            name: device.name().map(str::to_owned),
+            has_touch: has_key(KeyCode::BTN_TOUCH) || has_axis(AbsoluteAxisCode::ABS_MT_POSITION_X),
+            has_abs_xy: (has_axis(AbsoluteAxisCode::ABS_X) && has_axis(AbsoluteAxisCode::ABS_Y))
+                || (has_axis(AbsoluteAxisCode::ABS_MT_POSITION_X)
+                    && has_axis(AbsoluteAxisCode::ABS_MT_POSITION_Y)),
+            has_rel: device
+                .supported_relative_axes()
+                .is_some_and(|axes| axes.iter().next().is_some()),
+            is_keyboard: has_key(KeyCode::KEY_A) && has_key(KeyCode::KEY_ENTER),
+        }
+    }
+
+    /// True if the device looks like a touchscreen, reporting both touch contact and absolute
+    /// positions
+    pub fn is_touchscreen(&self) -> bool {
+        self.has_touch && self.has_abs_xy
+    }
+}
+
+/// List the event devices under `/dev/input`, sorted by path
+///
+/// Devices which cannot be opened, e.g. for lack of permissions, are skipped. If `/dev/input`
+/// does not exist, no devices are listed.
+pub fn list_devices() -> Result<Vec<DeviceInfo>, Error> {
+    let entries = match std::fs::read_dir("/dev/input") {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut devices = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let is_event_node = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("event"));
+        if !is_event_node {
+            continue;
+        }
+        match evdev::Device::open(&path) {
+            Ok(device) => devices.push(DeviceInfo::from_device(&path, &device)),
+            Err(e) => log::debug!("Skipping input device {path:?}: {e}"),
+        }
+    }
+    devices.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(devices)
+}
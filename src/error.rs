@@ -0,0 +1,53 @@
+use std::{fmt, path::PathBuf};
+
+use evdev::{AbsoluteAxisCode, KeyCode};
+
+/// Errors reported when opening or reading an input device
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// An I/O error from the device or the operating system
+    Io(std::io::Error),
+    /// No input device exists at the given path
+    DeviceNotFound(PathBuf),
+    /// The device does not report an axis which is required
+    MissingAxis(AbsoluteAxisCode),
+    /// The device does not report a key which is required, such as `BTN_TOUCH` for contact
+    MissingKey(KeyCode),
+    /// A scale factor which is not finite and positive was given when opening a device
+    InvalidScaleFactor(f32),
+    /// The screen size could not be read from the given file, and must be set explicitly
+    ScreenSizeUnavailable(PathBuf),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+            Self::DeviceNotFound(path) => write!(f, "no input device found at {path:?}"),
+            Self::MissingAxis(axis) => write!(f, "device does not report axis {axis:?}"),
+            Self::MissingKey(key) => write!(f, "device does not report key {key:?}"),
+            Self::InvalidScaleFactor(scale_factor) => {
+                write!(f, "invalid scale factor {scale_factor}")
+            }
+            Self::ScreenSizeUnavailable(path) => {
+                write!(f, "cannot read the screen size from {path:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
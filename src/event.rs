@@ -0,0 +1,146 @@
+use evdev::FetchEventsSynced;
+use slint::{
+    LogicalPosition,
+    platform::{PointerEventButton, WindowEvent},
+};
+
+use crate::{Collector, SlintEventsIterator};
+
+/// An event carrying more information than slint's [`WindowEvent`] can express
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ExtendedEvent {
+    /// A plain window event, from a device with no additional information to report
+    Window(WindowEvent),
+    /// A window event from a stylus, with the pen state at the time of the event
+    Stylus(StylusEvent),
+    /// A gesture recognised from the contacts on the device
+    Gesture(GestureEvent),
+    /// A move of a pressed contact, reported in place of `PointerMoved` when
+    /// [`Config::drag_events()`](crate::Config::drag_events) is enabled
+    Drag(DragEvent),
+}
+
+/// A move of a pressed contact, with the button it was pressed with
+#[derive(Clone, Debug, PartialEq)]
+pub struct DragEvent {
+    /// The new position of the contact
+    pub position: LogicalPosition,
+    /// The button reported by the press which started the drag
+    pub button: PointerEventButton,
+}
+
+/// A higher level event derived from the contacts on the device
+///
+/// Gesture events are reported after the pointer events generated by the same frame.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum GestureEvent {
+    /// The number of contacts on the device changed
+    ///
+    /// On multitouch devices this counts the occupied slots, otherwise it is 0 or 1. It is
+    /// reported once per change, on the frame where the change takes effect.
+    ContactCountChanged {
+        /// The number of contacts now on the device
+        count: usize,
+    },
+    /// A contact was pressed and released within one cell of the
+    /// [`tap_grid()`](crate::Config::tap_grid)
+    CellTapped {
+        /// Row of the cell, counting from 0 at the top
+        row: usize,
+        /// Column of the cell, counting from 0 at the left
+        col: usize,
+    },
+}
+
+/// The tool in use on a stylus device, from its `BTN_TOOL_*` keys
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StylusTool {
+    /// The device has not reported a tool
+    #[default]
+    Unknown,
+    /// A finger, from `BTN_TOOL_FINGER`
+    Finger,
+    /// The tip of a pen, from `BTN_TOOL_PEN`
+    Pen,
+    /// The eraser end of a pen, from `BTN_TOOL_RUBBER`
+    Eraser,
+}
+
+/// The modifier keys held on the device
+///
+/// Each modifier is held while either its left or right key is down.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    /// `KEY_LEFTSHIFT` or `KEY_RIGHTSHIFT`
+    pub shift: bool,
+    /// `KEY_LEFTCTRL` or `KEY_RIGHTCTRL`
+    pub control: bool,
+    /// `KEY_LEFTALT` or `KEY_RIGHTALT`
+    pub alt: bool,
+    /// `KEY_LEFTMETA` or `KEY_RIGHTMETA`
+    pub meta: bool,
+}
+
+/// A window event from a stylus
+#[derive(Clone, Debug, PartialEq)]
+pub struct StylusEvent {
+    /// The pointer event
+    pub event: WindowEvent,
+    /// The tool most recently brought into range
+    pub tool: StylusTool,
+    /// The raw `ABS_PRESSURE` value, if the device has reported one
+    pub pressure: Option<i32>,
+    /// The raw `ABS_TILT_X` value
+    pub tilt_x: i32,
+    /// The raw `ABS_TILT_Y` value
+    pub tilt_y: i32,
+}
+
+/// A window event numbered in the order it was returned
+///
+/// Sequence numbers start at 0 when the device is opened and increase by one for each sequenced
+/// event returned, so a gap shows that an event was lost between the reader and e.g. a log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SequencedEvent {
+    /// The position of this event among the sequenced events returned for the device
+    pub sequence: u64,
+    /// The window event
+    pub event: WindowEvent,
+}
+
+/// An iterator over sequenced events which will block until a new event is ready
+pub struct SlintSequencedIterator<'a> {
+    pub(crate) events: SlintEventsIterator<'a>,
+}
+
+impl Iterator for SlintSequencedIterator<'_> {
+    type Item = SequencedEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let event = self.events.next()?;
+        Some(SequencedEvent {
+            sequence: self.events.collector.next_sequence(),
+            event,
+        })
+    }
+}
+
+/// An iterator over extended events which will block until a new event is ready
+pub struct SlintExtendedIterator<'a> {
+    pub(crate) inner: Option<FetchEventsSynced<'a>>,
+    pub(crate) collector: &'a mut Collector,
+}
+
+impl Iterator for SlintExtendedIterator<'_> {
+    type Item = ExtendedEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner.as_mut() {
+            Some(inner) => self.collector.next_extended_from(inner),
+            None => self.collector.next_extended_from(&mut std::iter::empty()),
+        }
+    }
+}
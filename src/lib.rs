@@ -22,90 +22,85 @@
 //! [`into_event_stream()`](SlintEventsWrapper::into_event_stream) to create an
 //! [`EventStream`](tokio::EventStream).
 //!
+//! Code which handles events can be written against the [`WindowEventSource`] trait, and tested
+//! with a scripted [`MockEventSource`] in place of a device.
+//!
+//! Available devices can be listed with [`list_devices()`], e.g. to let a user choose a
+//! touchscreen.
+//!
+//! Recorded or hand-built events can be converted without a device using
+//! [`SlintEventsConverter`] or [`convert_events()`], and events from other sources can be pushed
+//! one at a time into an [`EventCollector`].
+//!
+//! When rendering with slint's `MinimalSoftwareWindow`, enabling the `software-renderer` feature
+//! adds [`pump_to_window()`](SlintEventsWrapper::pump_to_window), which fetches events and
+//! dispatches them directly to the window, and [`run_event_loop()`], which runs a complete
+//! render and input loop for the window.
+//!
+//! Events are produced as slint's [`WindowEvent`], which is re-exported from `i-slint-core`, so
+//! they can be passed directly to code built on `i-slint-core`. Applications with their own event
+//! type can implement `From<WindowEvent>` for it and use
+//! [`fetch_events_as()`](SlintEventsWrapper::fetch_events_as), or
+//! [`next_event_as()`](tokio::EventStream::next_event_as) with the `tokio` feature.
+//!
 #![warn(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
-use std::path::Path;
-
-use evdev::{AbsoluteAxisCode, EventSummary, FetchEventsSynced, KeyCode};
-use slint::{
-    LogicalPosition, PhysicalPosition,
-    platform::{PointerEventButton, WindowEvent},
+use std::{
+    os::fd::{AsFd, OwnedFd},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
 };
 
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
-enum ButtonChange {
-    #[default]
-    None,
-    Up,
-    Down,
-}
-
-/// Collect evdev events and convert them to slint events
-struct Collector {
-    last_position: (i32, i32),
-    scale_factor: f32,
-    button_change: ButtonChange,
-}
+use evdev::{AbsoluteAxisCode, FetchEventsSynced, KeyCode};
+use nix::poll::{PollFd, PollFlags, PollTimeout};
+use slint::{LogicalPosition, platform::WindowEvent};
 
-impl Collector {
-    pub fn new(scale_factor: f32, last_position: (i32, i32)) -> Self {
-        Self {
-            last_position,
-            scale_factor,
-            button_change: ButtonChange::None,
-        }
-    }
-
-    pub fn push(&mut self, event: evdev::EventSummary) -> Option<WindowEvent> {
-        match event {
-            EventSummary::Synchronization(_, _, _) => {
-                let button_change = self.button_change;
-                self.button_change = ButtonChange::None;
-                if button_change == ButtonChange::Down {
-                    return Some(WindowEvent::PointerPressed {
-                        position: self.last_logical_position(),
-                        button: PointerEventButton::Left,
-                    });
-                } else if button_change == ButtonChange::Up {
-                    return Some(WindowEvent::PointerReleased {
-                        position: self.last_logical_position(),
-                        button: PointerEventButton::Left,
-                    });
-                } else {
-                    return Some(WindowEvent::PointerMoved {
-                        position: self.last_logical_position(),
-                    });
-                };
-            }
-            EventSummary::AbsoluteAxis(_event, code, value) => match code {
-                AbsoluteAxisCode::ABS_X => self.last_position.0 = value,
-                AbsoluteAxisCode::ABS_Y => self.last_position.1 = value,
-                _ => (),
-            },
-            EventSummary::Key(_event, key, value) => {
-                if matches!(key, KeyCode::BTN_TOUCH) {
-                    if value == 1 {
-                        self.button_change = ButtonChange::Down
-                    } else {
-                        self.button_change = ButtonChange::Up;
-                    }
-                }
-            }
-            _ => (),
-        }
-        None
-    }
+mod collector;
+mod config;
+mod convert;
+mod devices;
+mod error;
+mod event;
+mod multitouch;
+mod priority;
+mod shared;
+mod shutdown;
+#[cfg(feature = "software-renderer")]
+mod software;
+mod source;
 
-    fn last_logical_position(&self) -> LogicalPosition {
-        let (x, y) = self.last_position;
-        LogicalPosition::from_physical(PhysicalPosition::new(x, y), self.scale_factor)
-    }
-}
+use collector::Collector;
+pub use collector::{Capabilities, ContactSource, Metrics};
+pub use config::{
+    Config, CoordinateMode, Distance, EdgeMargin, OverflowPolicy, Rounding, ScrollEdge,
+    ScrollSurface, StickPointer, TapGrid,
+};
+pub use convert::{EventCollector, SlintEventsConverter, convert_events};
+pub use devices::{DeviceInfo, list_devices};
+pub use error::Error;
+pub use event::{
+    DragEvent, ExtendedEvent, GestureEvent, Modifiers, SequencedEvent, SlintExtendedIterator,
+    SlintSequencedIterator, StylusEvent, StylusTool,
+};
+pub use multitouch::{MultiTouchEvent, SlintMultiTouchIterator};
+pub use priority::ThreadPriority;
+pub use shared::ConfigHandle;
+use shared::ConfigSync;
+use shutdown::Shutdown;
+pub use shutdown::{ShutdownHandle, SlintEventsUntilShutdown};
+#[cfg(feature = "software-renderer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "software-renderer")))]
+pub use software::run_event_loop;
+pub use source::{
+    AsyncWindowEventSource, MapPositions, MockEventSource, WindowEventSource, WindowEventsExt,
+};
 
 /// A wrapper for evdev::Device to convert events to slint WindowEvents
 ///
-/// Only supports single-touch touch screens
+/// Events are reported for a single pointer. Per-contact events for multitouch screens are
+/// available from [`fetch_multitouch()`](Self::fetch_multitouch).
 ///
 /// # Example
 ///
@@ -125,8 +120,15 @@ impl Collector {
 /// ```
 pub struct SlintEventsWrapper {
     device: evdev::Device,
-    last_position: (i32, i32),
-    scale_factor: f32,
+    collector: Collector,
+    /// The path as given when the wrapper was created, which may be a symlink
+    path: PathBuf,
+    /// The event device node the path resolved to when the device was last opened
+    resolved_path: PathBuf,
+    /// Created on the first call to `shutdown_handle()`
+    shutdown: Option<Arc<Shutdown>>,
+    /// Created on the first call to `config_handle()`
+    config_sync: Option<ConfigSync>,
 }
 
 impl SlintEventsWrapper {
@@ -137,80 +139,811 @@ impl SlintEventsWrapper {
     /// - `device`: A path to the device (e.g. '/dev/input/event0')
     /// - `scale_factor`: The scale factor from slint for converting between logical and physical
     ///   coordinates.
-    pub fn new(device: impl AsRef<Path>, scale_factor: f32) -> std::io::Result<Self> {
-        let device = evdev::Device::open(device)?;
+    ///
+    /// Touch contact is normally read from `BTN_TOUCH`. For type-B multitouch devices which do not
+    /// report `BTN_TOUCH`, contact is instead derived from the `ABS_MT_TRACKING_ID` of the first
+    /// finger down, for type-A devices from the first contact reported, and otherwise from
+    /// `BTN_TOOL_FINGER` if supported. See
+    /// [`Config::contact_keys()`] to select the contact keys explicitly.
+    ///
+    /// Returns [`Error::InvalidScaleFactor`] if `scale_factor` is not finite and positive.
+    pub fn new(device: impl AsRef<Path>, scale_factor: f32) -> Result<Self, Error> {
+        Self::with_config(device, Config::new(scale_factor))
+    }
+
+    /// Create a new SlintEventsWrapper using the given event device path and [`Config`]
+    ///
+    /// The path is canonicalized before opening, so symlinks are resolved to the event node. The
+    /// position is read from the device's current axis values, so that events are reported at the
+    /// real position before the device next reports a move.
+    ///
+    /// Returns [`Error::DeviceNotFound`] if nothing exists at the path, and
+    /// [`Error::InvalidScaleFactor`] if the configured scale factor is not finite and positive.
+    pub fn with_config(device: impl AsRef<Path>, config: Config) -> Result<Self, Error> {
+        config.check()?;
+        let path = device.as_ref().to_path_buf();
+        let resolved_path = resolve(&path)?;
+        let device = evdev::Device::open(&resolved_path)?;
+        check_device(&device, &config)?;
+        let caps = detect_capabilities(&device, &config)?;
+        let mut collector = Collector::new(config, caps);
+        seed_position(&device, &mut collector);
         Ok(Self {
             device,
-            last_position: (0, 0),
-            scale_factor,
+            collector,
+            path,
+            resolved_path,
+            shutdown: None,
+            config_sync: None,
         })
     }
 
+    /// Create a new SlintEventsWrapper from a symlink to an event device
+    ///
+    /// Event node numbering may change across reboots, so devices are best identified by the
+    /// symlinks under `/dev/input/by-id` or `/dev/input/by-path`. The event node the link resolves
+    /// to is logged, and the link is resolved again by [`reconnect()`](Self::reconnect).
+    pub fn from_link(link: impl AsRef<Path>, config: Config) -> Result<Self, Error> {
+        let wrapper = Self::with_config(link, config)?;
+        log::info!(
+            "Input device {:?} resolved to {:?}",
+            wrapper.path,
+            wrapper.resolved_path
+        );
+        Ok(wrapper)
+    }
+
+    /// Create a new SlintEventsWrapper from an already open event device
+    ///
+    /// This allows device access to be delegated, e.g. by a privileged broker which opens the
+    /// device and passes the descriptor to a sandboxed process. The wrapper takes ownership of
+    /// `fd` and closes it when dropped.
+    ///
+    /// A wrapper created this way has no path, so [`path()`](Self::path) and
+    /// [`resolved_path()`](Self::resolved_path) are empty and [`reconnect()`](Self::reconnect)
+    /// fails.
+    pub fn from_fd(fd: OwnedFd, scale_factor: f32) -> Result<Self, Error> {
+        Self::from_fd_with_config(fd, Config::new(scale_factor))
+    }
+
+    /// Create a new SlintEventsWrapper from an already open event device and [`Config`]
+    ///
+    /// See [`from_fd()`](Self::from_fd).
+    pub fn from_fd_with_config(fd: OwnedFd, config: Config) -> Result<Self, Error> {
+        config.check()?;
+        let device = evdev::Device::from_fd(fd)?;
+        check_device(&device, &config)?;
+        let caps = detect_capabilities(&device, &config)?;
+        let mut collector = Collector::new(config, caps);
+        seed_position(&device, &mut collector);
+        Ok(Self {
+            device,
+            collector,
+            path: PathBuf::new(),
+            resolved_path: PathBuf::new(),
+            shutdown: None,
+            config_sync: None,
+        })
+    }
+
+    /// Re-resolve the device path and reopen the device
+    ///
+    /// This can be used to recover after a device has been disconnected, or when a symlink has
+    /// changed its target. Configuration is retained.
+    ///
+    /// Wrappers created with [`from_fd()`](Self::from_fd) have no path to reopen, and return an
+    /// [`Error::Io`] with an [`Unsupported`](std::io::ErrorKind::Unsupported) error.
+    pub fn reconnect(&mut self) -> Result<(), Error> {
+        if self.path.as_os_str().is_empty() {
+            return Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "device was opened from a file descriptor and cannot be reopened",
+            )));
+        }
+        let resolved_path = resolve(&self.path)?;
+        if resolved_path != self.resolved_path {
+            log::info!(
+                "Input device {:?} now resolves to {:?}",
+                self.path,
+                resolved_path
+            );
+        }
+        let device = evdev::Device::open(&resolved_path)?;
+        check_device(&device, self.collector.config())?;
+        self.device = device;
+        self.collector
+            .set_capabilities(detect_capabilities(&self.device, self.collector.config())?);
+        seed_position(&self.device, &mut self.collector);
+        self.resolved_path = resolved_path;
+        Ok(())
+    }
+
+    /// Reset the tracked position and contact state
+    ///
+    /// Any pending press or release is discarded, and the position is read again from the
+    /// device's current axis values, so a stale position from before e.g. hiding the UI is not
+    /// reported with the next event. The device only reports an axis when its value changes, so
+    /// without this a contact moving along one axis would not be reported.
+    pub fn reset_state(&mut self) {
+        self.collector.reset();
+        seed_position(&self.device, &mut self.collector);
+    }
+
+    /// An event re-asserting the current contact, if it is pressed
+    ///
+    /// After a pause in handling events, e.g. while a modal was shown, this lets the UI catch up
+    /// with a finger which is still down. The press is reported at the contact's current position,
+    /// as of the events read so far. Returns `None` if no contact is pressed.
+    pub fn replay_state(&self) -> Option<WindowEvent> {
+        self.collector.replay_state()
+    }
+
+    /// Stop emitting window events, without closing the device
+    ///
+    /// While paused, events are still read and the position and contact are tracked, but no window
+    /// events are returned, e.g. so that touches during a transition animation are ignored.
+    /// Events already generated but not yet returned are discarded.
+    pub fn pause(&mut self) {
+        self.collector.pause(std::iter::empty());
+    }
+
+    /// Emit window events again after [`pause()`](Self::pause)
+    ///
+    /// Returns the event which brings the window up to date with the contact, which may be
+    /// dispatched or ignored: a release if the contact was lifted while paused, the press of a
+    /// contact pressed while paused, or otherwise a move to the current position. Returns `None`
+    /// if not paused.
+    pub fn resume(&mut self) -> Option<WindowEvent> {
+        self.collector.resume()
+    }
+
+    /// True while paused by [`pause()`](Self::pause)
+    pub fn is_paused(&self) -> bool {
+        self.collector.is_paused()
+    }
+
+    /// The configuration used to convert events
+    ///
+    /// Changes made through a [`ConfigHandle`] are reflected here once they have been picked up
+    /// by a read.
+    pub fn config(&self) -> &Config {
+        self.collector.config()
+    }
+
+    /// Get a handle which can be used to change the configuration from another thread
+    ///
+    /// See [`ConfigHandle`].
+    pub fn config_handle(&mut self) -> ConfigHandle {
+        self.config_sync
+            .get_or_insert_with(|| ConfigSync::new(&self.collector))
+            .handle()
+    }
+
+    /// Convert a logical position to the raw values of the device's X and Y axes
+    ///
+    /// This is the inverse of the conversion applied to reported positions, taking the coordinate
+    /// mode, swapped axes, and scale factor into account, e.g. for aligning a calibration overlay
+    /// with the device's counts. Values are rounded to the nearest count.
+    pub fn logical_to_raw(&self, position: LogicalPosition) -> (i32, i32) {
+        self.collector.raw_position(position)
+    }
+
+    /// The logical position of the pointer, as of the last event read
+    ///
+    /// This is the position the next move would report, updated as each event is read rather than
+    /// only when a window event is returned. It is rounded as configured. Together with
+    /// [`last_raw_position()`](Self::last_raw_position), it can be used to draw a debugging
+    /// crosshair which shows both the device's counts and where they are mapped to.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use slint_evdev_input::SlintEventsWrapper;
+    ///
+    /// let mut slint_device = SlintEventsWrapper::new("/dev/input/event0", 1.0).unwrap();
+    /// loop {
+    ///     for _event in slint_device.fetch_events() {}
+    ///     let position = slint_device.current_position();
+    ///     let (raw_x, raw_y) = slint_device.last_raw_position();
+    ///     // Draw the crosshair at `position`, labelled with the raw counts
+    ///     println!("({}, {}) from ({raw_x}, {raw_y})", position.x, position.y);
+    /// }
+    /// ```
+    pub fn current_position(&self) -> LogicalPosition {
+        self.collector.current_position()
+    }
+
+    /// The raw values of the device's X and Y axes, as of the last event read
+    ///
+    /// Values are in the device's order, before [`swap_axes()`](Config::swap_axes) is applied, as
+    /// returned by [`logical_to_raw()`](Self::logical_to_raw).
+    pub fn last_raw_position(&self) -> (i32, i32) {
+        self.collector.last_raw_position()
+    }
+
+    /// The modifier keys held on the device, as of the last event read
+    ///
+    /// Slint's pointer events do not carry modifiers, so for e.g. shift-click on a device with its
+    /// own shift key, either check this when handling the click, or enable
+    /// [`Config::forward_modifiers()`].
+    pub fn current_modifiers(&self) -> Modifiers {
+        self.collector.current_modifiers()
+    }
+
+    /// Where touches at the corners of the device would be reported, without touching it
+    ///
+    /// The corners of the axis ranges reported by the device are converted as touches would be,
+    /// in the order top-left, top-right, bottom-right, bottom-left of the raw axes, i.e. (min, min),
+    /// (max, min), (max, max), (min, max). This shows mistakes in the coordinate mode, swapped
+    /// axes, or offset at a glance. Returns `None` if the device does not report its axis ranges.
+    pub fn calibration_preview(&self) -> Option<[LogicalPosition; 4]> {
+        self.collector.corner_positions()
+    }
+
+    /// The capabilities detected when the device was opened
+    pub fn capabilities(&self) -> &Capabilities {
+        self.collector.capabilities()
+    }
+
+    /// Counts of the events processed so far
+    ///
+    /// Counting costs a few integer additions per event, whether or not this is called.
+    pub fn metrics(&self) -> Metrics {
+        self.collector.metrics()
+    }
+
+    /// The device path as given when the wrapper was created
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The event device node which the path resolved to when the device was last opened
+    pub fn resolved_path(&self) -> &Path {
+        &self.resolved_path
+    }
+
     /// Fetches and returns event. This will block until events are ready.
+    ///
+    /// If shutdown is requested via a [`ShutdownHandle`], this returns an empty iterator.
     pub fn fetch_events<'a>(&'a mut self) -> SlintEventsIterator<'a> {
-        SlintEventsIterator {
-            inner: self.device.fetch_events().unwrap(),
-            collector: Collector::new(self.scale_factor, self.last_position),
+        self.read_events().unwrap()
+    }
+
+    /// [`fetch_events()`](Self::fetch_events), returning the error if reading from the device fails
+    fn read_events<'a>(&'a mut self) -> Result<SlintEventsIterator<'a>, Error> {
+        // Queued events are returned without waiting for the device
+        let inner = if self.collector.has_queued() {
+            None
+        } else {
+            fetch(&mut self.device, self.shutdown.as_deref())?
+        };
+        shared::sync(&mut self.config_sync, &mut self.collector);
+        Ok(SlintEventsIterator {
+            inner,
+            collector: &mut self.collector,
+        })
+    }
+
+    /// Fetches and returns events numbered in sequence. This will block until events are ready.
+    ///
+    /// This reports the same events as [`fetch_events()`](Self::fetch_events), each with a
+    /// [`SequencedEvent::sequence`] number one greater than the last, e.g. for checking the order
+    /// of events passed between threads.
+    ///
+    /// Returns the error if reading from the device fails, e.g. when it is unplugged.
+    pub fn fetch_sequenced<'a>(&'a mut self) -> Result<SlintSequencedIterator<'a>, Error> {
+        Ok(SlintSequencedIterator {
+            events: self.read_events()?,
+        })
+    }
+
+    /// Fetches events converted to an application event type. This will block until events are
+    /// ready.
+    ///
+    /// This is [`fetch_events()`](Self::fetch_events) with each event converted by `E::from()`.
+    /// Returns the error if reading from the device fails, e.g. when it is unplugged.
+    ///
+    /// ```no_run
+    /// use slint::platform::WindowEvent;
+    /// use slint_evdev_input::SlintEventsWrapper;
+    ///
+    /// struct AppEvent(WindowEvent);
+    ///
+    /// impl From<WindowEvent> for AppEvent {
+    ///     fn from(event: WindowEvent) -> Self {
+    ///         Self(event)
+    ///     }
+    /// }
+    ///
+    /// let mut slint_device = SlintEventsWrapper::new("/dev/input/event0", 1.0).unwrap();
+    /// for AppEvent(event) in slint_device.fetch_events_as::<AppEvent>().unwrap() {
+    ///     println!("{:?}", event);
+    /// }
+    /// ```
+    pub fn fetch_events_as<E: From<WindowEvent>>(
+        &mut self,
+    ) -> Result<impl Iterator<Item = E>, Error> {
+        Ok(self.read_events()?.map(E::from))
+    }
+
+    /// Fetches events into `buf`, replacing its contents. This will block until events are ready.
+    ///
+    /// This performs a single device read, like [`fetch_events()`](Self::fetch_events), but allows
+    /// one buffer to be reused across calls rather than collecting from an iterator.
+    pub fn fetch_events_into(&mut self, buf: &mut Vec<WindowEvent>) -> Result<(), Error> {
+        buf.clear();
+        buf.extend(self.collector.drain_queued());
+        if !buf.is_empty() {
+            return Ok(());
+        }
+        if let Some(mut events) = fetch(&mut self.device, self.shutdown.as_deref())? {
+            shared::sync(&mut self.config_sync, &mut self.collector);
+            while let Some(event) = self.collector.next_from(&mut events) {
+                buf.push(event);
+            }
+            buf.extend(self.collector.flush_read());
+            buf.extend(self.collector.drain_queued());
+        }
+        Ok(())
+    }
+
+    /// Fetches and returns per-contact events from a multitouch device. This will block until
+    /// events are ready.
+    ///
+    /// Each contact is reported with its own `PointerPressed`, `PointerMoved`, and
+    /// `PointerReleased` events, tagged with an id which is stable for the duration of the contact.
+    ///
+    /// Returns the error if reading from the device fails, e.g. when it is unplugged.
+    pub fn fetch_multitouch<'a>(&'a mut self) -> Result<SlintMultiTouchIterator<'a>, Error> {
+        // Queued events are returned without waiting for the device
+        let inner = if self.collector.has_queued() {
+            None
+        } else {
+            fetch(&mut self.device, self.shutdown.as_deref())?
+        };
+        shared::sync(&mut self.config_sync, &mut self.collector);
+        Ok(SlintMultiTouchIterator {
+            inner,
+            collector: &mut self.collector,
+        })
+    }
+
+    /// Fetches and returns extended events. This will block until events are ready.
+    ///
+    /// This reports the same events as [`fetch_events()`](Self::fetch_events), along with
+    /// information slint's events cannot carry, such as the tilt of a stylus. Devices with nothing
+    /// more to report produce [`ExtendedEvent::Window`] events.
+    ///
+    /// Returns the error if reading from the device fails, e.g. when it is unplugged.
+    pub fn fetch_extended<'a>(&'a mut self) -> Result<SlintExtendedIterator<'a>, Error> {
+        // Queued events are returned without waiting for the device
+        let inner = if self.collector.has_queued() {
+            None
+        } else {
+            fetch(&mut self.device, self.shutdown.as_deref())?
+        };
+        shared::sync(&mut self.config_sync, &mut self.collector);
+        Ok(SlintExtendedIterator {
+            inner,
+            collector: &mut self.collector,
+        })
+    }
+
+    /// Fetches and returns events, blocking for at most `timeout` while waiting for them.
+    ///
+    /// If no events become ready before the timeout elapses, an empty iterator is returned.
+    pub fn fetch_events_timeout<'a>(
+        &'a mut self,
+        timeout: Duration,
+    ) -> Result<SlintEventsIterator<'a>, Error> {
+        let timeout = PollTimeout::try_from(timeout).unwrap_or(PollTimeout::MAX);
+        let inner = if self.collector.has_queued() {
+            None
+        } else if poll_readable(&self.device, self.shutdown.as_deref(), timeout)? {
+            Some(self.device.fetch_events()?)
+        } else {
+            None
+        };
+        shared::sync(&mut self.config_sync, &mut self.collector);
+        Ok(SlintEventsIterator {
+            inner,
+            collector: &mut self.collector,
+        })
+    }
+
+    /// Read all input waiting on the device without blocking, collapsing it into a single event
+    ///
+    /// This fast-forwards through a backlog of input, e.g. after the application has stalled, so
+    /// that an obsolete drag path is not replayed. Intermediate moves are discarded, and the
+    /// backlog is reported as a single `PointerMoved` to the final position. If the backlog changed
+    /// whether a contact is pressed, the final press or release is reported instead, and a tap
+    /// which began and ended within the backlog is dropped. Returns `None` if no input was waiting.
+    pub fn drain_latest(&mut self) -> Result<Option<WindowEvent>, Error> {
+        let was_pressed = self.collector.replay_state().is_some();
+        let mut events: Vec<_> = self.collector.drain_queued().collect();
+        while poll_readable(&self.device, self.shutdown.as_deref(), PollTimeout::ZERO)? {
+            let mut inner = self.device.fetch_events()?;
+            shared::sync(&mut self.config_sync, &mut self.collector);
+            while let Some(event) = self.collector.next_from(&mut inner) {
+                events.push(event);
+            }
+            events.extend(self.collector.flush_read());
+            events.extend(self.collector.drain_queued());
         }
+        Ok(self.collector.collapse(events, was_pressed))
+    }
+
+    /// Check whether the device has input waiting to be read, without blocking or consuming it
+    ///
+    /// When this returns `true`, [`fetch_events()`](Self::fetch_events) will not block.
+    pub fn has_pending(&self) -> Result<bool, Error> {
+        Ok(poll_readable(
+            &self.device,
+            self.shutdown.as_deref(),
+            PollTimeout::ZERO,
+        )?)
+    }
+
+    /// Get a handle which can be used to stop reading from another thread
+    ///
+    /// Once shutdown is requested, blocked reads wake and return no events, and
+    /// [`events_until_shutdown()`](Self::events_until_shutdown) ends.
+    pub fn shutdown_handle(&mut self) -> Result<ShutdownHandle, Error> {
+        let shutdown = match &self.shutdown {
+            Some(shutdown) => shutdown.clone(),
+            None => self.shutdown.insert(Arc::new(Shutdown::new()?)).clone(),
+        };
+        Ok(ShutdownHandle(shutdown))
+    }
+
+    /// True if shutdown has been requested via a [`ShutdownHandle`]
+    pub fn is_shutdown(&self) -> bool {
+        self.shutdown
+            .as_ref()
+            .is_some_and(|shutdown| shutdown.is_requested())
+    }
+
+    /// Returns an iterator which reads events from the device until shutdown is requested
+    ///
+    /// Unlike [`fetch_events()`](Self::fetch_events), this continues across device reads, blocking
+    /// as needed. It ends when shutdown is requested via a [`ShutdownHandle`], or on a device
+    /// error, which is logged.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use slint_evdev_input::SlintEventsWrapper;
+    ///
+    /// let mut slint_device = SlintEventsWrapper::new("/dev/input/event0", 1.0).unwrap();
+    /// let shutdown = slint_device.shutdown_handle().unwrap();
+    /// let reader = std::thread::spawn(move || {
+    ///     for event in slint_device.events_until_shutdown() {
+    ///         println!("{:?}", event);
+    ///     }
+    /// });
+    ///
+    /// // Later, during teardown
+    /// shutdown.shutdown();
+    /// reader.join().unwrap();
+    /// ```
+    pub fn events_until_shutdown(&mut self) -> SlintEventsUntilShutdown<'_> {
+        SlintEventsUntilShutdown {
+            wrapper: self,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Spawn a thread which reads events from the device and sends them to `sender`
+    ///
+    /// The thread owns the wrapper, and reads until the receiver is dropped, shutdown is requested
+    /// via a [`ShutdownHandle`] taken beforehand, or reading from the device fails. A dropped
+    /// receiver is noticed when the next event is sent. The thread returns the error which ended
+    /// it, if any.
+    ///
+    /// See [`EventStream::spawn_into_channel()`](tokio::EventStream::spawn_into_channel) for an
+    /// async equivalent.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use slint_evdev_input::SlintEventsWrapper;
+    ///
+    /// let (sender, receiver) = std::sync::mpsc::channel();
+    /// let slint_device = SlintEventsWrapper::new("/dev/input/event0", 1.0).unwrap();
+    /// let reader = slint_device.spawn_into_channel(sender);
+    /// for event in receiver {
+    ///     println!("{:?}", event);
+    /// }
+    /// reader.join().unwrap().unwrap();
+    /// ```
+    pub fn spawn_into_channel(
+        self,
+        sender: std::sync::mpsc::Sender<WindowEvent>,
+    ) -> std::thread::JoinHandle<Result<(), Error>> {
+        self.spawn_reader(sender, None)
+    }
+
+    /// Spawn a thread as [`spawn_into_channel()`](Self::spawn_into_channel) does, running at
+    /// `priority`
+    ///
+    /// Elevated priority reduces input latency when the system is under load. If the process is
+    /// not permitted to set the priority, e.g. it lacks `CAP_SYS_NICE`, a warning is logged and
+    /// the thread runs at normal priority.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use slint_evdev_input::{SlintEventsWrapper, ThreadPriority};
+    ///
+    /// let (sender, receiver) = std::sync::mpsc::channel();
+    /// let slint_device = SlintEventsWrapper::new("/dev/input/event0", 1.0).unwrap();
+    /// let reader = slint_device.spawn_into_channel_with_priority(sender, ThreadPriority::Fifo(50));
+    /// for event in receiver {
+    ///     println!("{:?}", event);
+    /// }
+    /// reader.join().unwrap().unwrap();
+    /// ```
+    pub fn spawn_into_channel_with_priority(
+        self,
+        sender: std::sync::mpsc::Sender<WindowEvent>,
+        priority: ThreadPriority,
+    ) -> std::thread::JoinHandle<Result<(), Error>> {
+        self.spawn_reader(sender, Some(priority))
+    }
+
+    fn spawn_reader(
+        mut self,
+        sender: std::sync::mpsc::Sender<WindowEvent>,
+        priority: Option<ThreadPriority>,
+    ) -> std::thread::JoinHandle<Result<(), Error>> {
+        std::thread::spawn(move || {
+            if let Some(priority) = priority {
+                priority.apply();
+            }
+            let mut events = Vec::new();
+            while !self.is_shutdown() {
+                self.fetch_events_into(&mut events)?;
+                for event in events.drain(..) {
+                    if sender.send(event).is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Fetches events and dispatches each of them to `window`. This will block until events are
+    /// ready.
+    ///
+    /// Returns the number of events dispatched, or the error if reading from the device fails,
+    /// e.g. when it is unplugged.
+    ///
+    /// Requires the `software-renderer` feature
+    #[cfg(feature = "software-renderer")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "software-renderer")))]
+    pub fn pump_to_window(
+        &mut self,
+        window: &slint::platform::software_renderer::MinimalSoftwareWindow,
+    ) -> Result<usize, Error> {
+        let inner = if self.collector.has_queued() {
+            None
+        } else {
+            fetch(&mut self.device, self.shutdown.as_deref())?
+        };
+        shared::sync(&mut self.config_sync, &mut self.collector);
+        let events = SlintEventsIterator {
+            inner,
+            collector: &mut self.collector,
+        };
+        let mut count = 0;
+        for event in events {
+            window.dispatch_event(event);
+            count += 1;
+        }
+        Ok(count)
     }
 
     /// Convert the wrapper into an [`EventStream`](tokio::EventStream) for async reading
     ///
+    /// This must be called from within a tokio runtime with I/O enabled, e.g. one built with
+    /// `enable_io()` or `enable_all()`. Outside of any runtime an [`Error::Io`] explaining this is
+    /// returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics within tokio if the runtime does not have I/O enabled, since tokio offers no way to
+    /// check for this beforehand.
+    ///
     /// Requires the `tokio` feature
     #[cfg(feature = "tokio")]
     #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
-    pub fn into_event_stream(self) -> std::io::Result<tokio::EventStream> {
+    pub fn into_event_stream(self) -> Result<tokio::EventStream, Error> {
+        if ::tokio::runtime::Handle::try_current().is_err() {
+            return Err(Error::Io(std::io::Error::other(
+                "into_event_stream() must be called within a tokio runtime with I/O enabled",
+            )));
+        }
         Ok(tokio::EventStream {
             evdev_stream: self.device.into_event_stream()?,
-            collector: Collector::new(self.scale_factor, self.last_position),
+            collector: self.collector,
+            pending: None,
+            last_move: None,
+            config_sync: self.config_sync,
+            repeat: None,
+            stick_from: None,
+            held_since: None,
+            coalescing: None,
+            queue: std::collections::VecDeque::new(),
         })
     }
 }
 
+/// Resolve a device path to the event node it refers to
+fn resolve(path: &Path) -> Result<PathBuf, Error> {
+    std::fs::canonicalize(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => Error::DeviceNotFound(path.to_path_buf()),
+        _ => Error::Io(e),
+    })
+}
+
+/// Check that a device looks like a touchscreen, unless the config allows any device
+fn check_device(device: &evdev::Device, config: &Config) -> Result<(), Error> {
+    if config.allow_any_device {
+        return Ok(());
+    }
+    let has_axis = |axis| {
+        device
+            .supported_absolute_axes()
+            .is_some_and(|axes| axes.contains(axis))
+    };
+    if let Some(stick) = &config.stick_pointer {
+        // The stick drives the pointer without touch axes or contact
+        for axis in [stick.x_axis, stick.y_axis] {
+            if !has_axis(axis) {
+                return Err(Error::MissingAxis(axis));
+            }
+        }
+        return Ok(());
+    }
+    let has_key = |key| {
+        device
+            .supported_keys()
+            .is_some_and(|keys| keys.contains(key))
+    };
+    let has_mt_position = has_axis(AbsoluteAxisCode::ABS_MT_POSITION_X)
+        && has_axis(AbsoluteAxisCode::ABS_MT_POSITION_Y);
+    if config.position_handler.is_none() && !has_mt_position {
+        for axis in [config.x_axis, config.y_axis] {
+            if !has_axis(axis) {
+                return Err(Error::MissingAxis(axis));
+            }
+        }
+    }
+    let has_contact = match config.contact_keys.first() {
+        Some(&first) if !config.contact_keys.iter().any(|&key| has_key(key)) => {
+            return Err(Error::MissingKey(first));
+        }
+        Some(_) => true,
+        None => {
+            has_key(KeyCode::BTN_TOUCH)
+                || has_key(KeyCode::BTN_TOOL_FINGER)
+                || has_axis(AbsoluteAxisCode::ABS_MT_TRACKING_ID)
+                || has_mt_position
+        }
+    };
+    if !has_contact {
+        return Err(Error::MissingKey(KeyCode::BTN_TOUCH));
+    }
+    Ok(())
+}
+
+/// Read the capabilities of the device, with position on the axes set in `config`
+fn detect_capabilities(device: &evdev::Device, config: &Config) -> Result<Capabilities, Error> {
+    Capabilities::detect_with_axes(device, config.x_axis, config.y_axis)
+}
+
+/// Seed the position with the current values of the device's position axes, so that the first
+/// events report the real position rather than the origin
+fn seed_position(device: &evdev::Device, collector: &mut Collector) {
+    let state = match device.get_abs_state() {
+        Ok(state) => state,
+        Err(e) => {
+            log::debug!("Cannot read the current position of the device: {e}");
+            return;
+        }
+    };
+    let stick_axes = collector
+        .config()
+        .stick_pointer
+        .map(|stick| [stick.x_axis, stick.y_axis]);
+    let value = |axes: [AbsoluteAxisCode; 2]| {
+        axes.into_iter()
+            .find(|&axis| {
+                device
+                    .supported_absolute_axes()
+                    .is_some_and(|supported| supported.contains(axis))
+            })
+            .filter(|axis| !stick_axes.is_some_and(|stick_axes| stick_axes.contains(axis)))
+            .map(|axis| state[axis.0 as usize].value)
+    };
+    let config = collector.config();
+    let x = value([config.x_axis, AbsoluteAxisCode::ABS_MT_POSITION_X]);
+    let y = value([config.y_axis, AbsoluteAxisCode::ABS_MT_POSITION_Y]);
+    collector.seed_position(x, y);
+}
+
+/// Block until events are read from the device
+///
+/// Returns `None` without reading if shutdown is requested.
+fn fetch<'a>(
+    device: &'a mut evdev::Device,
+    shutdown: Option<&Shutdown>,
+) -> std::io::Result<Option<FetchEventsSynced<'a>>> {
+    if shutdown.is_some() {
+        // Wait for either the device or a shutdown request
+        while !poll_readable(device, shutdown, PollTimeout::NONE)? {
+            if shutdown.is_some_and(|shutdown| shutdown.is_requested()) {
+                return Ok(None);
+            }
+        }
+    }
+    device.fetch_events().map(Some)
+}
+
+/// Wait up to `timeout` for the device to become readable
+///
+/// Returns `false` on timeout, or if shutdown has been requested.
+fn poll_readable(
+    device: &evdev::Device,
+    shutdown: Option<&Shutdown>,
+    timeout: PollTimeout,
+) -> std::io::Result<bool> {
+    let result = match shutdown {
+        Some(shutdown) => {
+            if shutdown.is_requested() {
+                return Ok(false);
+            }
+            let mut fds = [
+                PollFd::new(device.as_fd(), PollFlags::POLLIN),
+                PollFd::new(shutdown.as_fd(), PollFlags::POLLIN),
+            ];
+            nix::poll::poll(&mut fds, timeout)
+                .map(|_| fds[0].any().unwrap_or(false) && !shutdown.is_requested())
+        }
+        None => {
+            let mut fds = [PollFd::new(device.as_fd(), PollFlags::POLLIN)];
+            nix::poll::poll(&mut fds, timeout).map(|n| n > 0)
+        }
+    };
+    match result {
+        Ok(readable) => Ok(readable),
+        // A signal arriving during the wait is treated the same as a timeout
+        Err(nix::errno::Errno::EINTR) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
 /// An iterator over window events which will block until a new event is ready
 pub struct SlintEventsIterator<'a> {
-    inner: FetchEventsSynced<'a>,
-    collector: Collector,
+    inner: Option<FetchEventsSynced<'a>>,
+    collector: &'a mut Collector,
 }
 
 impl Iterator for SlintEventsIterator<'_> {
     type Item = WindowEvent;
 
     fn next(&mut self) -> Option<Self::Item> {
-        // Read to sync event
-        loop {
-            match self.inner.next() {
-                Some(event) => {
-                    if let Some(window_event) = self.collector.push(event.destructure()) {
-                        return Some(window_event);
-                    }
-                }
-                None => return None,
-            }
+        match self.inner.as_mut() {
+            Some(inner) => self
+                .collector
+                .next_from(inner)
+                .or_else(|| self.collector.flush_read()),
+            None => self.collector.take_queued(),
         }
     }
 }
 
 #[cfg(feature = "tokio")]
 #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
-/// Tokio support
-pub mod tokio {
-    use super::*;
-    /// A async stream of input events
-    pub struct EventStream {
-        pub(super) evdev_stream: evdev::EventStream,
-        pub(super) collector: Collector,
-    }
-
-    impl EventStream {
-        /// Get a future for the next available event in the stream
-        pub async fn next_event(&mut self) -> Result<WindowEvent, std::io::Error> {
-            loop {
-                let event = self.evdev_stream.next_event().await?;
-                if let Some(ret) = self.collector.push(event.destructure()) {
-                    return Ok(ret);
-                }
-            }
-        }
-    }
-}
+pub mod tokio;
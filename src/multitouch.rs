@@ -0,0 +1,38 @@
+use evdev::FetchEventsSynced;
+use slint::platform::WindowEvent;
+
+use crate::Collector;
+
+/// A window event for one contact of a multitouch device
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultiTouchEvent {
+    /// Identifies the contact for the duration of its lifecycle, from `PointerPressed` to
+    /// `PointerReleased`
+    ///
+    /// This is the `ABS_MT_TRACKING_ID` assigned to the contact by the device. Type-A devices
+    /// which do not report tracking IDs are identified by their position in each report, which
+    /// only remains stable while contacts are neither added nor removed ahead of them.
+    pub id: i32,
+    /// The `PointerPressed`, `PointerMoved`, or `PointerReleased` event for the contact
+    pub event: WindowEvent,
+    /// The last `ABS_MT_PRESSURE` reported for the contact, or `ABS_PRESSURE` for devices without
+    /// slots, if the device reports it
+    pub pressure: Option<i32>,
+}
+
+/// An iterator over per-contact events which will block until a new event is ready
+pub struct SlintMultiTouchIterator<'a> {
+    pub(crate) inner: Option<FetchEventsSynced<'a>>,
+    pub(crate) collector: &'a mut Collector,
+}
+
+impl Iterator for SlintMultiTouchIterator<'_> {
+    type Item = MultiTouchEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner.as_mut() {
+            Some(inner) => self.collector.next_multitouch_from(inner),
+            None => self.collector.next_multitouch_from(&mut std::iter::empty()),
+        }
+    }
+}
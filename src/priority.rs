@@ -0,0 +1,51 @@
+/// Scheduling priority for a reader thread
+///
+/// See
+/// [`SlintEventsWrapper::spawn_into_channel_with_priority()`](crate::SlintEventsWrapper::spawn_into_channel_with_priority).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThreadPriority {
+    /// Real-time `SCHED_FIFO` scheduling at the given priority, from 1 (lowest) to 99 (highest)
+    ///
+    /// Requires `CAP_SYS_NICE`, or an `RLIMIT_RTPRIO` allowing the priority.
+    Fifo(i32),
+    /// Normal scheduling with the given niceness, from -20 (highest priority) to 19 (lowest)
+    ///
+    /// Raising the priority above the default of 0 requires `CAP_SYS_NICE`, or an
+    /// `RLIMIT_NICE` allowing it.
+    Nice(i32),
+}
+
+impl ThreadPriority {
+    /// Apply the priority to the calling thread, logging a warning if it is not permitted
+    pub(crate) fn apply(self) {
+        let result = match self {
+            Self::Fifo(priority) => {
+                let param = libc::sched_param {
+                    sched_priority: priority,
+                };
+                // SAFETY: `param` is a valid sched_param for the duration of the call
+                match unsafe {
+                    libc::pthread_setschedparam(libc::pthread_self(), libc::SCHED_FIFO, &param)
+                } {
+                    0 => Ok(()),
+                    errno => Err(std::io::Error::from_raw_os_error(errno)),
+                }
+            }
+            Self::Nice(niceness) => {
+                // On Linux, niceness set for a thread ID applies to that thread only
+                // SAFETY: Plain system calls with no pointers
+                match unsafe {
+                    libc::setpriority(libc::PRIO_PROCESS as _, libc::gettid() as _, niceness)
+                } {
+                    0 => Ok(()),
+                    _ => Err(std::io::Error::last_os_error()),
+                }
+            }
+        };
+        if let Err(e) = result {
+            log::warn!(
+                "Cannot set reader thread priority {self:?}, continuing at normal priority: {e}"
+            );
+        }
+    }
+}
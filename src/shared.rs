@@ -0,0 +1,104 @@
+use std::sync::{
+    Arc, RwLock,
+    atomic::{AtomicU64, Ordering},
+};
+
+use crate::{Collector, Config};
+
+/// Configuration shared between a reader and its [`ConfigHandle`]s
+pub(crate) struct SharedConfig {
+    config: RwLock<Config>,
+    /// Incremented on each change, so the reader can check for changes without locking
+    generation: AtomicU64,
+}
+
+/// A handle for changing the configuration of a [`SlintEventsWrapper`](crate::SlintEventsWrapper)
+/// from another thread
+///
+/// Changes are picked up by the reader after its next read from the device, and apply from the
+/// next frame boundary onwards, so no frame is converted partly with each configuration. The
+/// tracked position and contact state are kept. This allows e.g. a settings screen to recalibrate
+/// the running reader, by changing the [`coordinate_mode()`](Config::coordinate_mode) or
+/// [`offset()`](Config::offset), without reopening the device.
+///
+/// Obtained from [`SlintEventsWrapper::config_handle()`](crate::SlintEventsWrapper::config_handle).
+///
+/// # Example
+///
+/// ```no_run
+/// use slint_evdev_input::SlintEventsWrapper;
+///
+/// let mut slint_device = SlintEventsWrapper::new("/dev/input/event0", 1.0).unwrap();
+/// let config = slint_device.config_handle();
+/// std::thread::spawn(move || {
+///     loop {
+///         for event in slint_device.fetch_events() {
+///             println!("{:?}", event);
+///         }
+///     }
+/// });
+///
+/// // Later, from the UI thread
+/// config.update(|config| config.scale_factor(2.0));
+/// ```
+#[derive(Clone)]
+pub struct ConfigHandle(pub(crate) Arc<SharedConfig>);
+
+impl ConfigHandle {
+    /// A copy of the current configuration
+    pub fn get(&self) -> Config {
+        self.0.config.read().unwrap().clone()
+    }
+
+    /// Replace the configuration
+    pub fn set(&self, config: Config) {
+        self.update(|_| config);
+    }
+
+    /// Modify the configuration, e.g. with the builder methods of [`Config`]
+    pub fn update(&self, f: impl FnOnce(Config) -> Config) {
+        let mut config = self.0.config.write().unwrap();
+        *config = f(config.clone());
+        self.0.generation.fetch_add(1, Ordering::Release);
+    }
+}
+
+/// The reader's side of a shared configuration
+pub(crate) struct ConfigSync {
+    shared: Arc<SharedConfig>,
+    /// The generation last applied to the collector
+    generation: u64,
+}
+
+impl ConfigSync {
+    /// Share the configuration of `collector`
+    pub fn new(collector: &Collector) -> Self {
+        Self {
+            shared: Arc::new(SharedConfig {
+                config: RwLock::new(collector.config().clone()),
+                generation: AtomicU64::new(0),
+            }),
+            generation: 0,
+        }
+    }
+
+    pub fn handle(&self) -> ConfigHandle {
+        ConfigHandle(self.shared.clone())
+    }
+
+    /// Apply the shared configuration to `collector` if it has changed
+    pub fn apply(&mut self, collector: &mut Collector) {
+        let generation = self.shared.generation.load(Ordering::Acquire);
+        if generation != self.generation {
+            self.generation = generation;
+            collector.set_config(self.shared.config.read().unwrap().clone());
+        }
+    }
+}
+
+/// Apply changes made through a [`ConfigHandle`], if the configuration is shared
+pub(crate) fn sync(config_sync: &mut Option<ConfigSync>, collector: &mut Collector) {
+    if let Some(config_sync) = config_sync {
+        config_sync.apply(collector);
+    }
+}
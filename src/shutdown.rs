@@ -0,0 +1,91 @@
+use std::{
+    os::fd::{AsFd, BorrowedFd},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use nix::sys::eventfd::{EfdFlags, EventFd};
+use slint::platform::WindowEvent;
+
+use crate::SlintEventsWrapper;
+
+/// Shared state used to wake a blocked read when shutdown is requested
+pub(crate) struct Shutdown {
+    eventfd: EventFd,
+    requested: AtomicBool,
+}
+
+impl Shutdown {
+    pub fn new() -> std::io::Result<Self> {
+        Ok(Self {
+            eventfd: EventFd::from_flags(EfdFlags::EFD_CLOEXEC | EfdFlags::EFD_NONBLOCK)?,
+            requested: AtomicBool::new(false),
+        })
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::Acquire)
+    }
+}
+
+impl AsFd for Shutdown {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.eventfd.as_fd()
+    }
+}
+
+/// A handle for stopping a [`SlintEventsWrapper`] from another thread
+///
+/// Requesting shutdown wakes any blocked read, which then returns no events. Once requested,
+/// shutdown cannot be undone, and all further reads return no events.
+///
+/// Obtained from [`SlintEventsWrapper::shutdown_handle()`].
+#[derive(Clone)]
+pub struct ShutdownHandle(pub(crate) Arc<Shutdown>);
+
+impl ShutdownHandle {
+    /// Request shutdown, waking the reader if it is blocked
+    pub fn shutdown(&self) {
+        self.0.requested.store(true, Ordering::Release);
+        // The eventfd is never read, so it stays readable. Writing only fails if the counter
+        // would overflow, in which case it is already readable.
+        let _ = self.0.eventfd.write(1);
+    }
+
+    /// True if shutdown has been requested
+    pub fn is_shutdown(&self) -> bool {
+        self.0.is_requested()
+    }
+}
+
+/// An iterator over window events which reads from the device until shutdown is requested
+///
+/// Obtained from [`SlintEventsWrapper::events_until_shutdown()`].
+pub struct SlintEventsUntilShutdown<'a> {
+    pub(crate) wrapper: &'a mut SlintEventsWrapper,
+    /// The events of the last read still to be returned, in reverse order so that the next is
+    /// popped from the end
+    pub(crate) buf: Vec<WindowEvent>,
+}
+
+impl Iterator for SlintEventsUntilShutdown<'_> {
+    type Item = WindowEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.buf.pop() {
+                return Some(event);
+            }
+            if self.wrapper.is_shutdown() {
+                return None;
+            }
+            if let Err(e) = self.wrapper.fetch_events_into(&mut self.buf) {
+                log::error!("Error reading input device: {e}");
+                return None;
+            }
+            self.buf.reverse();
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Render and input loops for slint's `MinimalSoftwareWindow`
+
+use std::time::Duration;
+
+use slint::platform::software_renderer::{MinimalSoftwareWindow, SoftwareRenderer};
+
+use crate::{Error, SlintEventsWrapper};
+
+/// Run a render and input loop for `window`, reading input from `wrapper`
+///
+/// Each pass updates slint's timers and animations, calls `draw_fn` if the window needs to be
+/// redrawn, and then waits for input, dispatching any events read to the window. Input is
+/// dispatched before the frame which shows its effect is rendered. While the window is animating
+/// the loop does not wait for input, and otherwise it waits no longer than the next timer is due.
+///
+/// `draw_fn` is passed the window's renderer, with which it renders into the application's
+/// framebuffer.
+///
+/// Returns once shutdown is requested via a [`ShutdownHandle`](crate::ShutdownHandle), or when
+/// reading from the device fails.
+///
+/// Requires the `software-renderer` feature. See
+/// [`tokio::run_event_loop()`](crate::tokio::run_event_loop) for an async equivalent.
+///
+/// # Example
+///
+/// ```no_run
+/// use slint::platform::software_renderer::{MinimalSoftwareWindow, RepaintBufferType, Rgb565Pixel};
+/// use slint_evdev_input::SlintEventsWrapper;
+///
+/// let window = MinimalSoftwareWindow::new(RepaintBufferType::ReusedBuffer);
+/// let mut framebuffer = vec![Rgb565Pixel::default(); 320 * 240];
+/// let mut slint_device = SlintEventsWrapper::new("/dev/input/event0", 1.0).unwrap();
+/// slint_evdev_input::run_event_loop(&window, &mut slint_device, |renderer| {
+///     renderer.render(&mut framebuffer, 320);
+///     // Copy the framebuffer to the display here
+/// })
+/// .unwrap();
+/// ```
+pub fn run_event_loop(
+    window: &MinimalSoftwareWindow,
+    wrapper: &mut SlintEventsWrapper,
+    mut draw_fn: impl FnMut(&SoftwareRenderer),
+) -> Result<(), Error> {
+    loop {
+        slint::platform::update_timers_and_animations();
+        window.draw_if_needed(&mut draw_fn);
+        if wrapper.is_shutdown() {
+            return Ok(());
+        }
+        for event in wrapper.fetch_events_timeout(next_frame_timeout(window))? {
+            window.dispatch_event(event);
+        }
+    }
+}
+
+/// How long to wait for input before the window needs drawing again
+pub(crate) fn next_frame_timeout(window: &MinimalSoftwareWindow) -> Duration {
+    if window.has_active_animations() {
+        Duration::ZERO
+    } else {
+        slint::platform::duration_until_next_timer_update().unwrap_or(Duration::MAX)
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::VecDeque;
+
+use evdev::InputEvent;
+use slint::{LogicalPosition, platform::WindowEvent};
+
+use crate::{Capabilities, Collector, Config, Error, SlintEventsWrapper};
+
+/// A source of window events
+///
+/// This is implemented by [`SlintEventsWrapper`], and by [`MockEventSource`] for testing code
+/// which handles events without a device. Events from a single read, such as those of
+/// [`SlintEventsWrapper::fetch_events()`], a [`SlintEventsConverter`](crate::SlintEventsConverter),
+/// or a [`MockEventSource`], can also be taken one at a time as an [`Iterator`].
+pub trait WindowEventSource {
+    /// Fetches events into `buf`, replacing its contents
+    fn fetch_events_into(&mut self, buf: &mut Vec<WindowEvent>) -> Result<(), Error>;
+
+    /// Fetches events into a new `Vec`
+    fn fetch_window_events(&mut self) -> Result<Vec<WindowEvent>, Error> {
+        let mut buf = Vec::new();
+        self.fetch_events_into(&mut buf)?;
+        Ok(buf)
+    }
+}
+
+impl WindowEventSource for SlintEventsWrapper {
+    /// See [`SlintEventsWrapper::fetch_events_into()`]
+    fn fetch_events_into(&mut self, buf: &mut Vec<WindowEvent>) -> Result<(), Error> {
+        SlintEventsWrapper::fetch_events_into(self, buf)
+    }
+}
+
+/// A source of window events which are awaited one at a time
+///
+/// This is the async counterpart of [`WindowEventSource`], implemented by
+/// [`EventStream`](crate::tokio::EventStream) with the `tokio` feature.
+pub trait AsyncWindowEventSource {
+    /// Wait for the next event
+    fn next_event(&mut self) -> impl Future<Output = Result<WindowEvent, Error>>;
+}
+
+/// A scripted [`WindowEventSource`] for tests
+///
+/// Events are pushed in batches, and each fetch returns the next batch. Once all batches have been
+/// fetched, fetches return no events rather than blocking.
+///
+/// # Example
+///
+/// ```
+/// use evdev::{AbsoluteAxisCode, EventType, InputEvent, KeyCode, SynchronizationCode};
+/// use slint_evdev_input::{Config, MockEventSource, WindowEventSource};
+///
+/// let mut source = MockEventSource::new(Config::default());
+/// source.push_raw([
+///     InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+///     InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+///     InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+///     InputEvent::new(EventType::SYNCHRONIZATION.0, SynchronizationCode::SYN_REPORT.0, 0),
+/// ]);
+/// assert_eq!(1, source.fetch_window_events().unwrap().len());
+/// assert!(source.fetch_window_events().unwrap().is_empty());
+/// ```
+pub struct MockEventSource {
+    collector: Collector,
+    batches: VecDeque<VecDeque<WindowEvent>>,
+}
+
+impl MockEventSource {
+    /// Create a mock source converting raw events as from a single touch device
+    pub fn new(config: Config) -> Self {
+        Self::with_capabilities(config, Capabilities::default())
+    }
+
+    /// Create a mock source converting raw events as from a device with the given capabilities
+    pub fn with_capabilities(config: Config, caps: Capabilities) -> Self {
+        Self {
+            collector: Collector::new(config, caps),
+            batches: VecDeque::new(),
+        }
+    }
+
+    /// Add a batch of window events, to be returned as they are
+    pub fn push_events(&mut self, events: impl IntoIterator<Item = WindowEvent>) {
+        self.batches.push_back(events.into_iter().collect());
+    }
+
+    /// Add a batch of raw events, as read from a device
+    ///
+    /// The events are converted as they are pushed. Events following the last `SYN_REPORT` are
+    /// carried over into the next batch.
+    pub fn push_raw(&mut self, events: impl IntoIterator<Item = InputEvent>) {
+        let mut events = events.into_iter();
+        let mut batch = VecDeque::new();
+        while let Some(event) = self.collector.next_from(&mut events) {
+            batch.push_back(event);
+        }
+        self.batches.push_back(batch);
+    }
+
+    /// True if all batches have been fetched
+    pub fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+}
+
+impl Iterator for MockEventSource {
+    type Item = WindowEvent;
+
+    /// The next event of the current batch, moving on to the next batch once it is exhausted
+    fn next(&mut self) -> Option<WindowEvent> {
+        loop {
+            let batch = self.batches.front_mut()?;
+            if let Some(event) = batch.pop_front() {
+                return Some(event);
+            }
+            self.batches.pop_front();
+        }
+    }
+}
+
+impl WindowEventSource for MockEventSource {
+    fn fetch_events_into(&mut self, buf: &mut Vec<WindowEvent>) -> Result<(), Error> {
+        buf.clear();
+        buf.extend(self.batches.pop_front().into_iter().flatten());
+        Ok(())
+    }
+}
+
+/// Adds [`map_positions()`](Self::map_positions) to iterators of window events
+pub trait WindowEventsExt: Iterator<Item = WindowEvent> + Sized {
+    /// Apply `f` to the position of every event which has one
+    ///
+    /// This allows application-specific adjustments to positions after conversion. Events without
+    /// a position are passed through unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use slint::LogicalPosition;
+    /// use slint_evdev_input::{SlintEventsWrapper, WindowEventsExt};
+    ///
+    /// let mut slint_device = SlintEventsWrapper::new("/dev/input/event0", 1.0).unwrap();
+    /// // Mirror a screen mounted upside down
+    /// let events = slint_device
+    ///     .fetch_events()
+    ///     .map_positions(|position| LogicalPosition::new(800.0 - position.x, 480.0 - position.y));
+    /// for event in events {
+    ///     println!("{:?}", event);
+    /// }
+    /// ```
+    fn map_positions<F: FnMut(LogicalPosition) -> LogicalPosition>(
+        self,
+        f: F,
+    ) -> MapPositions<Self, F> {
+        MapPositions::new(self, f)
+    }
+}
+
+impl<I: Iterator<Item = WindowEvent>> WindowEventsExt for I {}
+
+/// Events from a source with their positions transformed by a function
+///
+/// Created by [`WindowEventsExt::map_positions()`], or by
+/// [`EventStream::map_positions()`](crate::tokio::EventStream::map_positions) for async reading.
+pub struct MapPositions<S, F> {
+    source: S,
+    f: F,
+}
+
+impl<S, F: FnMut(LogicalPosition) -> LogicalPosition> MapPositions<S, F> {
+    pub(crate) fn new(source: S, f: F) -> Self {
+        Self { source, f }
+    }
+
+    /// The underlying source of events
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+
+    /// Unwrap the underlying source of events
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+
+    fn map(&mut self, event: WindowEvent) -> WindowEvent {
+        let f = &mut self.f;
+        match event {
+            WindowEvent::PointerPressed { position, button } => WindowEvent::PointerPressed {
+                position: f(position),
+                button,
+            },
+            WindowEvent::PointerReleased { position, button } => WindowEvent::PointerReleased {
+                position: f(position),
+                button,
+            },
+            WindowEvent::PointerMoved { position } => WindowEvent::PointerMoved {
+                position: f(position),
+            },
+            WindowEvent::PointerScrolled {
+                position,
+                delta_x,
+                delta_y,
+            } => WindowEvent::PointerScrolled {
+                position: f(position),
+                delta_x,
+                delta_y,
+            },
+            event => event,
+        }
+    }
+}
+
+impl<I, F> Iterator for MapPositions<I, F>
+where
+    I: Iterator<Item = WindowEvent>,
+    F: FnMut(LogicalPosition) -> LogicalPosition,
+{
+    type Item = WindowEvent;
+
+    fn next(&mut self) -> Option<WindowEvent> {
+        let event = self.source.next()?;
+        Some(self.map(event))
+    }
+}
+
+impl<S, F> AsyncWindowEventSource for MapPositions<S, F>
+where
+    S: AsyncWindowEventSource,
+    F: FnMut(LogicalPosition) -> LogicalPosition,
+{
+    async fn next_event(&mut self) -> Result<WindowEvent, Error> {
+        let event = self.source.next_event().await?;
+        Ok(self.map(event))
+    }
+}
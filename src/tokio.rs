@@ -0,0 +1,788 @@
+//! Tokio support
+
+use std::{
+    collections::VecDeque,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use ::tokio::time::Instant;
+use slint::{LogicalPosition, SharedString, platform::WindowEvent};
+
+use crate::{
+    AsyncWindowEventSource, Collector, Error, MapPositions, Metrics, Modifiers, OverflowPolicy,
+    SequencedEvent,
+    shared::{self, ConfigSync},
+};
+
+/// A async stream of input events
+pub struct EventStream {
+    pub(super) evdev_stream: evdev::EventStream,
+    pub(super) collector: Collector,
+    /// An event read while coalescing moves, to be returned next
+    pub(super) pending: Option<WindowEvent>,
+    /// When the most recent move was returned
+    pub(super) last_move: Option<Instant>,
+    /// Shared with the [`ConfigHandle`](crate::ConfigHandle)s of the wrapper, if any
+    pub(super) config_sync: Option<ConfigSync>,
+    /// When the held key is next repeated, and its text
+    pub(super) repeat: Option<(Instant, SharedString)>,
+    /// When the stick last moved the pointer, or started to be deflected
+    pub(super) stick_from: Option<Instant>,
+    /// Events read ahead of the consumer, with the configured event queue limit
+    pub(super) queue: VecDeque<WindowEvent>,
+    /// When the pressed contact was pressed or last moved, and where it was then
+    pub(super) held_since: Option<(Instant, LogicalPosition)>,
+    /// The move held while coalescing, and when it is due to be returned
+    pub(super) coalescing: Option<(WindowEvent, Instant)>,
+}
+
+impl EventStream {
+    /// Get a future for the next available event in the stream
+    ///
+    /// If [`Config::coalesce_interval()`](crate::Config::coalesce_interval) is set, a move is held
+    /// for up to the interval, and replaced by any further moves arriving in that time. A press or
+    /// release ends the interval early, and is returned immediately after the held move.
+    ///
+    /// If [`Config::max_move_rate()`](crate::Config::max_move_rate) is set, a move is likewise
+    /// held until the rate allows it to be returned.
+    ///
+    /// If [`Config::key_repeat()`](crate::Config::key_repeat) is set, a held contact reported as a
+    /// key is repeated with `KeyPressRepeated` events.
+    ///
+    /// If [`Config::stick_pointer()`](crate::Config::stick_pointer) is set, the pointer is moved
+    /// by the stick while it is deflected.
+    ///
+    /// If [`Config::max_press_duration()`](crate::Config::max_press_duration) is set, a contact
+    /// held for longer without moving is released.
+    ///
+    /// The future is cancel safe: if it is dropped before completing, e.g. in a `tokio::select!`,
+    /// no event is lost, and a move being coalesced is returned by the next call.
+    pub async fn next_event(&mut self) -> Result<WindowEvent, Error> {
+        let event = self.next_unrepeated().await?;
+        self.track_timers(&event);
+        Ok(event)
+    }
+
+    /// Wait for the next event, then return it along with any further events available without
+    /// waiting, up to `max` events in all
+    ///
+    /// This avoids awaiting each event of a burst separately. The first event is returned as by
+    /// [`next_event()`](Self::next_event), and the rest as they are read, so moves among them are
+    /// not coalesced or rate limited, though the event queue limit still applies. Reading stops
+    /// once `max` events are collected, leaving any further input for the next call. If `max` is
+    /// 0, an empty `Vec` is returned without waiting.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run() -> Result<(), slint_evdev_input::Error> {
+    /// use slint_evdev_input::SlintEventsWrapper;
+    ///
+    /// let mut stream = SlintEventsWrapper::new("/dev/input/event0", 1.0)?.into_event_stream()?;
+    /// loop {
+    ///     for event in stream.next_events(32).await? {
+    ///         println!("{:?}", event);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn next_events(&mut self, max: usize) -> Result<Vec<WindowEvent>, Error> {
+        if max == 0 {
+            return Ok(Vec::new());
+        }
+        let mut events = vec![self.next_event().await?];
+        while events.len() < max {
+            let Some(event) = self.next_ready()? else {
+                break;
+            };
+            self.track_timers(&event);
+            events.push(event);
+        }
+        Ok(events)
+    }
+
+    /// The next event which can be returned without waiting, if any
+    fn next_ready(&mut self) -> Result<Option<WindowEvent>, Error> {
+        if let Some(event) = self
+            .pending
+            .take()
+            .or_else(|| self.queue.pop_front())
+            .or_else(|| self.collector.take_queued())
+        {
+            return Ok(Some(event));
+        }
+        shared::sync(&mut self.config_sync, &mut self.collector);
+        match self.push_ready()? {
+            Some(event) => self.enqueue_ready(event).map(Some),
+            None => Ok(self.collector.flush_read()),
+        }
+    }
+
+    /// Start or stop repeating the held key, and timing the held contact, as `event` presses,
+    /// moves, or releases it
+    fn track_timers(&mut self, event: &WindowEvent) {
+        let now = Instant::now();
+        match event {
+            WindowEvent::KeyPressed { text } if self.collector.is_contact_text(text) => {
+                self.repeat = self
+                    .collector
+                    .config()
+                    .key_repeat
+                    .map(|(delay, _)| (now + delay, text.clone()));
+            }
+            WindowEvent::KeyReleased { text } if self.collector.is_contact_text(text) => {
+                self.repeat = None;
+            }
+            WindowEvent::PointerPressed { position, .. } => {
+                self.held_since = Some((now, *position));
+            }
+            WindowEvent::PointerMoved { position } => {
+                if let Some((since, from)) = &mut self.held_since
+                    && from != position
+                {
+                    *since = now;
+                    *from = *position;
+                }
+            }
+            WindowEvent::PointerReleased { .. } => self.held_since = None,
+            _ => (),
+        }
+    }
+
+    /// When the held contact is released regardless, if it does not move
+    fn held_deadline(&self) -> Option<Instant> {
+        let duration = self.collector.config().max_press_duration?;
+        self.held_since.map(|(since, _)| since + duration)
+    }
+
+    /// The next event, other than the repeats of a held key
+    async fn next_unrepeated(&mut self) -> Result<WindowEvent, Error> {
+        if let Some((_, deadline)) = self.coalescing {
+            return self.coalesce_moves(deadline).await;
+        }
+        if let Some(event) = self.pending.take().or_else(|| self.queue.pop_front()) {
+            return Ok(event);
+        }
+        if let Some(event) = self.collector.take_queued() {
+            return Ok(event);
+        }
+        let event = loop {
+            let input = match self.timer_deadline() {
+                Some(deadline) => {
+                    match ::tokio::time::timeout_at(deadline, self.next_input()).await {
+                        Ok(input) => input?,
+                        Err(_) => match self.timer_event() {
+                            Some(event) => return Ok(event),
+                            None => continue,
+                        },
+                    }
+                }
+                None => self.next_input().await?,
+            };
+            if let Some(event) = input {
+                break event;
+            }
+        };
+        if !matches!(event, WindowEvent::PointerMoved { .. }) {
+            return Ok(event);
+        }
+        let config = self.collector.config();
+        let coalesce_deadline = config
+            .coalesce_interval
+            .map(|interval| Instant::now() + interval);
+        let rate_deadline = config
+            .max_move_rate
+            .map(|rate| Duration::from_secs_f32(1.0 / rate))
+            .zip(self.last_move)
+            .map(|(interval, last_move)| last_move + interval);
+        match coalesce_deadline.max(rate_deadline) {
+            Some(deadline) if deadline > Instant::now() => {
+                self.coalescing = Some((event, deadline));
+                self.coalesce_moves(deadline).await
+            }
+            _ => {
+                self.last_move = Some(Instant::now());
+                Ok(event)
+            }
+        }
+    }
+
+    /// When the next timer event is due, if any
+    fn timer_deadline(&mut self) -> Option<Instant> {
+        let repeat_deadline = self.repeat.as_ref().map(|(deadline, _)| *deadline);
+        self.stick_deadline()
+            .into_iter()
+            .chain(repeat_deadline)
+            .chain(self.held_deadline())
+            .min()
+    }
+
+    /// When the stick next moves the pointer, if it is deflected
+    fn stick_deadline(&mut self) -> Option<Instant> {
+        let Some(tick) = self.collector.stick_tick() else {
+            self.stick_from = None;
+            return None;
+        };
+        Some(*self.stick_from.get_or_insert_with(Instant::now) + tick)
+    }
+
+    /// The event due when a deadline from [`timer_deadline()`](Self::timer_deadline) passes
+    fn timer_event(&mut self) -> Option<WindowEvent> {
+        let now = Instant::now();
+        if self.held_deadline().is_some_and(|deadline| deadline <= now) {
+            self.held_since = None;
+            if let Some(event) = self.collector.force_release() {
+                log::warn!("Contact held without moving for too long, releasing it");
+                return Some(event);
+            }
+        }
+        if let Some((deadline, text)) = self.repeat.clone()
+            && deadline <= now
+        {
+            let interval = self
+                .collector
+                .config()
+                .key_repeat
+                .map(|(_, interval)| interval);
+            self.repeat = interval.map(|interval| (deadline + interval, text.clone()));
+            return Some(WindowEvent::KeyPressRepeated { text });
+        }
+        let from = self.stick_from?;
+        let tick = self.collector.stick_tick()?;
+        if from + tick > now {
+            return None;
+        }
+        self.stick_from = Some(now);
+        self.collector.advance_stick(now - from)
+    }
+
+    /// Get a future for the next available event, converted to an application event type
+    ///
+    /// This is [`next_event()`](Self::next_event) with the event converted by `E::from()`.
+    pub async fn next_event_as<E: From<WindowEvent>>(&mut self) -> Result<E, Error> {
+        self.next_event().await.map(E::from)
+    }
+
+    /// Get a future for the next available event, numbered in sequence
+    ///
+    /// This is [`next_event()`](Self::next_event) with a sequence number, continuing from any
+    /// numbered by [`SlintEventsWrapper::fetch_sequenced()`](crate::SlintEventsWrapper::fetch_sequenced)
+    /// before the stream was created.
+    pub async fn next_sequenced(&mut self) -> Result<SequencedEvent, Error> {
+        let event = self.next_event().await?;
+        Ok(SequencedEvent {
+            sequence: self.collector.next_sequence(),
+            event,
+        })
+    }
+
+    /// Wrap the stream to apply `f` to the position of every event which has one
+    ///
+    /// Events are read from the wrapper with [`AsyncWindowEventSource::next_event()`]. See
+    /// [`WindowEventsExt::map_positions()`](crate::WindowEventsExt::map_positions).
+    pub fn map_positions<F: FnMut(LogicalPosition) -> LogicalPosition>(
+        self,
+        f: F,
+    ) -> MapPositions<Self, F> {
+        MapPositions::new(self, f)
+    }
+
+    /// Spawn a task which reads events from the stream and sends them to `sender`
+    ///
+    /// The task owns the stream, and reads until the receiver is dropped or reading from the device
+    /// fails. A dropped receiver is noticed when the next event is sent. The task returns the error
+    /// which ended it, if any. It must be called from within a tokio runtime.
+    ///
+    /// See [`SlintEventsWrapper::spawn_into_channel()`](crate::SlintEventsWrapper::spawn_into_channel)
+    /// for a blocking equivalent.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run() -> Result<(), slint_evdev_input::Error> {
+    /// use slint_evdev_input::SlintEventsWrapper;
+    ///
+    /// let (sender, mut receiver) = tokio::sync::mpsc::channel(64);
+    /// let stream = SlintEventsWrapper::new("/dev/input/event0", 1.0)?.into_event_stream()?;
+    /// let reader = stream.spawn_into_channel(sender);
+    /// while let Some(event) = receiver.recv().await {
+    ///     println!("{:?}", event);
+    /// }
+    /// reader.await.unwrap()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn spawn_into_channel(
+        mut self,
+        sender: ::tokio::sync::mpsc::Sender<WindowEvent>,
+    ) -> ::tokio::task::JoinHandle<Result<(), Error>> {
+        ::tokio::spawn(async move {
+            loop {
+                let event = self.next_event().await?;
+                if sender.send(event).await.is_err() {
+                    return Ok(());
+                }
+            }
+        })
+    }
+
+    /// Wait until the device has input ready to be read, or a timer event is due
+    ///
+    /// This pairs with [`drain()`](Self::drain) to dispatch events in batches: await `ready()`,
+    /// then drain everything available without awaiting each event. The first input event is read
+    /// while waiting, and is included in the next drain, as is a key repeat, a release of a contact
+    /// held too long, or a move by the stick which falls due while waiting.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run() -> Result<(), slint_evdev_input::Error> {
+    /// use slint_evdev_input::SlintEventsWrapper;
+    ///
+    /// let mut stream = SlintEventsWrapper::new("/dev/input/event0", 1.0)?.into_event_stream()?;
+    /// loop {
+    ///     stream.ready().await?;
+    ///     for event in stream.drain()? {
+    ///         println!("{:?}", event);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn ready(&mut self) -> Result<(), Error> {
+        if self.coalescing.is_some() || self.pending.is_some() || !self.queue.is_empty() {
+            return Ok(());
+        }
+        self.pending = self.collector.take_queued();
+        while self.pending.is_none() {
+            let event = match self.timer_deadline() {
+                Some(deadline) => {
+                    match ::tokio::time::timeout_at(deadline, self.evdev_stream.next_event()).await
+                    {
+                        Ok(event) => event?,
+                        Err(_) => {
+                            self.pending = self.timer_event();
+                            continue;
+                        }
+                    }
+                }
+                None => self.evdev_stream.next_event().await?,
+            };
+            shared::sync(&mut self.config_sync, &mut self.collector);
+            self.pending = self.collector.push(event.destructure());
+            break;
+        }
+        Ok(())
+    }
+
+    /// Return all events which can be read without waiting
+    ///
+    /// Returns an empty `Vec` if no input is ready and no timer event is due. Events are returned
+    /// as they are converted, so moves are not coalesced or rate limited as they are by
+    /// [`next_event()`](Self::next_event), but keys are repeated and contacts held too long are
+    /// released as configured.
+    pub fn drain(&mut self) -> Result<Vec<WindowEvent>, Error> {
+        let mut events: Vec<_> = self
+            .coalescing
+            .take()
+            .map(|(event, _)| event)
+            .into_iter()
+            .collect();
+        events.extend(self.pending.take());
+        events.extend(self.queue.drain(..));
+        events.extend(self.collector.drain_queued());
+        shared::sync(&mut self.config_sync, &mut self.collector);
+        while let Some(event) = self.push_ready()? {
+            events.push(event);
+            events.extend(self.collector.drain_queued());
+        }
+        events.extend(self.collector.flush_read());
+        events.extend(self.collector.drain_queued());
+        for event in &events {
+            self.track_timers(event);
+        }
+        if self
+            .timer_deadline()
+            .is_some_and(|deadline| deadline <= Instant::now())
+        {
+            events.extend(self.timer_event());
+        }
+        Ok(events)
+    }
+
+    /// An event re-asserting the current contact, if it is pressed
+    ///
+    /// See [`SlintEventsWrapper::replay_state()`](crate::SlintEventsWrapper::replay_state).
+    pub fn replay_state(&self) -> Option<WindowEvent> {
+        self.collector.replay_state()
+    }
+
+    /// The logical position of the pointer, as of the last event read
+    ///
+    /// See [`SlintEventsWrapper::current_position()`](crate::SlintEventsWrapper::current_position).
+    pub fn current_position(&self) -> LogicalPosition {
+        self.collector.current_position()
+    }
+
+    /// The raw values of the device's X and Y axes, as of the last event read
+    ///
+    /// See [`SlintEventsWrapper::last_raw_position()`](crate::SlintEventsWrapper::last_raw_position).
+    pub fn last_raw_position(&self) -> (i32, i32) {
+        self.collector.last_raw_position()
+    }
+
+    /// The modifier keys held on the device, as of the last event read
+    ///
+    /// See [`SlintEventsWrapper::current_modifiers()`](crate::SlintEventsWrapper::current_modifiers).
+    pub fn current_modifiers(&self) -> Modifiers {
+        self.collector.current_modifiers()
+    }
+
+    /// Counts of the events processed so far
+    ///
+    /// See [`SlintEventsWrapper::metrics()`](crate::SlintEventsWrapper::metrics).
+    pub fn metrics(&self) -> Metrics {
+        self.collector.metrics()
+    }
+
+    /// Stop emitting window events, without closing the device
+    ///
+    /// See [`SlintEventsWrapper::pause()`](crate::SlintEventsWrapper::pause). Events read ahead
+    /// but not yet returned are discarded, and a held key stops repeating.
+    pub fn pause(&mut self) {
+        let undelivered = self
+            .coalescing
+            .take()
+            .map(|(event, _)| event)
+            .into_iter()
+            .chain(self.pending.take())
+            .chain(self.queue.drain(..));
+        self.collector.pause(undelivered);
+        self.repeat = None;
+        self.held_since = None;
+    }
+
+    /// Emit window events again after [`pause()`](Self::pause)
+    ///
+    /// See [`SlintEventsWrapper::resume()`](crate::SlintEventsWrapper::resume).
+    pub fn resume(&mut self) -> Option<WindowEvent> {
+        self.collector.resume()
+    }
+
+    /// True while paused by [`pause()`](Self::pause)
+    pub fn is_paused(&self) -> bool {
+        self.collector.is_paused()
+    }
+
+    /// Reset the tracked position and contact state
+    ///
+    /// See [`SlintEventsWrapper::reset_state()`](crate::SlintEventsWrapper::reset_state).
+    pub fn reset_state(&mut self) {
+        self.collector.reset();
+        crate::seed_position(self.evdev_stream.device(), &mut self.collector);
+        self.pending = None;
+        self.last_move = None;
+        self.repeat = None;
+        self.stick_from = None;
+        self.queue.clear();
+        self.held_since = None;
+        self.coalescing = None;
+    }
+
+    /// Replace the held move with any moves arriving before `deadline`, then return it
+    ///
+    /// The move is held in `coalescing` rather than by the future, so that it is not lost if the
+    /// future is dropped.
+    async fn coalesce_moves(&mut self, deadline: Instant) -> Result<WindowEvent, Error> {
+        loop {
+            match ::tokio::time::timeout_at(deadline, self.next_collected()).await {
+                Err(_) => break,
+                Ok(Ok(event @ WindowEvent::PointerMoved { .. })) => {
+                    self.collector.count_coalesced_move();
+                    self.coalescing = Some((event, deadline));
+                }
+                Ok(Ok(event)) => {
+                    self.pending = Some(event);
+                    break;
+                }
+                Ok(Err(e)) => return Err(e),
+            }
+        }
+        let (event, _) = self
+            .coalescing
+            .take()
+            .expect("a move is held while coalescing");
+        self.last_move = Some(Instant::now());
+        Ok(event)
+    }
+
+    /// Read device events until the collector produces a window event, or returns `None` when
+    /// the stick starts moving the pointer
+    async fn next_input(&mut self) -> Result<Option<WindowEvent>, Error> {
+        if let Some(event) = self.queue.pop_front() {
+            return Ok(Some(event));
+        }
+        if let Some(event) = self.collector.take_queued() {
+            return Ok(Some(event));
+        }
+        let stick_idle = self.stick_from.is_none();
+        loop {
+            if let Some(ret) = self.push_next_input().await? {
+                return self.enqueue_ready(ret).map(Some);
+            }
+            if stick_idle && self.collector.stick_tick().is_some() {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Read device events until the collector produces a window event
+    async fn next_collected(&mut self) -> Result<WindowEvent, Error> {
+        if let Some(event) = self.queue.pop_front() {
+            return Ok(event);
+        }
+        if let Some(event) = self.collector.take_queued() {
+            return Ok(event);
+        }
+        loop {
+            if let Some(ret) = self.push_next_input().await? {
+                return self.enqueue_ready(ret);
+            }
+        }
+    }
+
+    /// Wait for a device event and push it to the collector, returning any window event produced
+    ///
+    /// With [`Config::flush_on_read()`](crate::Config::flush_on_read), the input which is ready
+    /// along with the event is pushed too, and its end treated as the end of a frame.
+    async fn push_next_input(&mut self) -> Result<Option<WindowEvent>, Error> {
+        let event = self.evdev_stream.next_event().await?;
+        shared::sync(&mut self.config_sync, &mut self.collector);
+        if let Some(event) = self.collector.push(event.destructure()) {
+            return Ok(Some(event));
+        }
+        if !self.collector.config().flush_on_read {
+            return Ok(None);
+        }
+        Ok(self.push_ready()?.or_else(|| self.collector.flush_read()))
+    }
+
+    /// Push the input which is ready to the collector until it produces a window event
+    ///
+    /// Returns `None` once no more input can be read without waiting.
+    fn push_ready(&mut self) -> Result<Option<WindowEvent>, Error> {
+        // Input which is not yet ready registers this no-op waker, which the next await replaces
+        let mut cx = Context::from_waker(Waker::noop());
+        while let Poll::Ready(event) = self.evdev_stream.poll_event(&mut cx) {
+            if let Some(event) = self.collector.push(event?.destructure()) {
+                return Ok(Some(event));
+            }
+        }
+        Ok(None)
+    }
+
+    /// With an event queue limit, queue `event` along with all the input which is ready after it,
+    /// and return the oldest event kept
+    fn enqueue_ready(&mut self, event: WindowEvent) -> Result<WindowEvent, Error> {
+        let Some((capacity, policy)) = self.collector.config().event_queue_limit else {
+            return Ok(event);
+        };
+        self.queue.push_back(event);
+        self.queue.extend(self.collector.drain_queued());
+        while let Some(event) = self.push_ready()? {
+            self.queue.push_back(event);
+            self.queue.extend(self.collector.drain_queued());
+            self.limit_queue(capacity, policy);
+        }
+        self.queue.extend(self.collector.flush_read());
+        self.limit_queue(capacity, policy);
+        Ok(self
+            .queue
+            .pop_front()
+            .expect("queue holds the event just read"))
+    }
+
+    /// Drop moves from the queue while it holds more than `capacity` events
+    fn limit_queue(&mut self, capacity: usize, policy: OverflowPolicy) {
+        if self.queue.len() <= capacity {
+            return;
+        }
+        let is_move = |event: &WindowEvent| matches!(event, WindowEvent::PointerMoved { .. });
+        let len = self.queue.len();
+        match policy {
+            OverflowPolicy::DropOldestMoves => {
+                while self.queue.len() > capacity {
+                    let Some(index) = self.queue.iter().position(is_move) else {
+                        break;
+                    };
+                    self.queue.remove(index);
+                }
+            }
+            OverflowPolicy::KeepLatestMove => {
+                let latest = self.queue.iter().rposition(is_move);
+                let mut index = 0;
+                self.queue.retain(|event| {
+                    let keep = !is_move(event) || Some(index) == latest;
+                    index += 1;
+                    keep
+                });
+            }
+        }
+        for _ in self.queue.len()..len {
+            self.collector.count_coalesced_move();
+        }
+    }
+}
+
+impl AsyncWindowEventSource for EventStream {
+    fn next_event(&mut self) -> impl Future<Output = Result<WindowEvent, Error>> {
+        EventStream::next_event(self)
+    }
+}
+
+/// A single async stream of the input events from several devices
+///
+/// Each device keeps its own conversion state and configuration, and events are tagged with the
+/// index of the device they came from, in the order the devices were added. Devices are polled in
+/// turn, starting after the device which produced the previous event, so a busy device cannot
+/// starve the others.
+///
+/// Each device's events are read with its [`EventStream::next_event()`], so its configuration
+/// applies as it would to the device alone, including the coalescing and rate limiting of moves
+/// and the timers such as key repeat.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> Result<(), slint_evdev_input::Error> {
+/// use slint_evdev_input::{SlintEventsWrapper, tokio::MultiEventStream};
+///
+/// let mut streams = MultiEventStream::new();
+/// for path in ["/dev/input/event0", "/dev/input/event1"] {
+///     streams.push(SlintEventsWrapper::new(path, 1.0)?.into_event_stream()?);
+/// }
+/// loop {
+///     let (index, event) = streams.next_event().await;
+///     println!("Device {index}: {:?}", event?);
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct MultiEventStream {
+    streams: Vec<EventStream>,
+    /// The index of the device to poll first
+    next: usize,
+}
+
+impl MultiEventStream {
+    /// Create a stream with no devices
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a device, returning the index its events are tagged with
+    pub fn push(&mut self, stream: EventStream) -> usize {
+        self.streams.push(stream);
+        self.streams.len() - 1
+    }
+
+    /// The number of devices
+    pub fn len(&self) -> usize {
+        self.streams.len()
+    }
+
+    /// True if no devices have been added
+    pub fn is_empty(&self) -> bool {
+        self.streams.is_empty()
+    }
+
+    /// The stream of the device with the given index
+    pub fn get(&self, index: usize) -> Option<&EventStream> {
+        self.streams.get(index)
+    }
+
+    /// The stream of the device with the given index, e.g. to reset its state
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut EventStream> {
+        self.streams.get_mut(index)
+    }
+
+    /// Get a future for the next event from any device, along with the index of the device
+    ///
+    /// An error reading a device is returned with the index of that device, and the other devices
+    /// can still be read. With no devices, the future never completes.
+    ///
+    /// The future is cancel safe, as each device's [`EventStream::next_event()`] is.
+    pub async fn next_event(&mut self) -> (usize, Result<WindowEvent, Error>) {
+        let count = self.streams.len();
+        let start = self.next;
+        // The futures of the devices which do not produce the event are dropped, which loses
+        // nothing since they are cancel safe
+        let mut futures: Vec<_> = self
+            .streams
+            .iter_mut()
+            .map(|stream| Box::pin(stream.next_event()))
+            .collect();
+        let (index, result) = std::future::poll_fn(|cx| {
+            for offset in 0..count {
+                let index = (start + offset) % count;
+                if let Poll::Ready(result) = futures[index].as_mut().poll(cx) {
+                    return Poll::Ready((index, result));
+                }
+            }
+            Poll::Pending
+        })
+        .await;
+        drop(futures);
+        self.next = (index + 1) % count;
+        (index, result)
+    }
+}
+
+/// Run a render and input loop for `window`, reading input from `stream`
+///
+/// The async equivalent of [`run_event_loop()`](crate::run_event_loop). Events which are ready
+/// together are all dispatched before the next frame is drawn, as they are read with
+/// [`EventStream::drain()`], so moves are not coalesced or rate limited.
+///
+/// Returns only when reading from the device fails. The loop can be stopped by dropping the
+/// future, e.g. from a `tokio::select!`.
+///
+/// Requires the `tokio` and `software-renderer` features
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> Result<(), slint_evdev_input::Error> {
+/// use slint::platform::software_renderer::{MinimalSoftwareWindow, RepaintBufferType, Rgb565Pixel};
+/// use slint_evdev_input::SlintEventsWrapper;
+///
+/// let window = MinimalSoftwareWindow::new(RepaintBufferType::ReusedBuffer);
+/// let mut framebuffer = vec![Rgb565Pixel::default(); 320 * 240];
+/// let mut stream = SlintEventsWrapper::new("/dev/input/event0", 1.0)?.into_event_stream()?;
+/// slint_evdev_input::tokio::run_event_loop(&window, &mut stream, |renderer| {
+///     renderer.render(&mut framebuffer, 320);
+///     // Copy the framebuffer to the display here
+/// })
+/// .await
+/// # }
+/// ```
+#[cfg(feature = "software-renderer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "software-renderer")))]
+pub async fn run_event_loop(
+    window: &slint::platform::software_renderer::MinimalSoftwareWindow,
+    stream: &mut EventStream,
+    mut draw_fn: impl FnMut(&slint::platform::software_renderer::SoftwareRenderer),
+) -> Result<(), Error> {
+    loop {
+        slint::platform::update_timers_and_animations();
+        window.draw_if_needed(&mut draw_fn);
+        let timeout = crate::software::next_frame_timeout(window);
+        // `ready()` only stores what it reads, so it is safe to abandon on timeout
+        if let Ok(ready) = ::tokio::time::timeout(timeout, stream.ready()).await {
+            ready?;
+            for event in stream.drain()? {
+                window.dispatch_event(event);
+            }
+        }
+    }
+}
@@ -0,0 +1,2533 @@
+use std::time::Duration;
+
+use evdev::{
+    AbsInfo, AbsoluteAxisCode, EventSummary, EventType, InputEvent, KeyCode, MiscCode,
+    SynchronizationCode,
+};
+use slint::{
+    LogicalPosition,
+    platform::{Key, PointerEventButton, WindowEvent},
+};
+use slint_evdev_input::{
+    Capabilities, Config, ContactSource, CoordinateMode, Distance, DragEvent, Error,
+    EventCollector, ExtendedEvent, GestureEvent, Metrics, Modifiers, MultiTouchEvent, Rounding,
+    ScrollEdge, SlintEventsConverter, SlintEventsWrapper, StickPointer, StylusEvent, StylusTool,
+    WindowEventsExt, convert_events,
+};
+
+fn abs(code: AbsoluteAxisCode, value: i32) -> InputEvent {
+    InputEvent::new(EventType::ABSOLUTE.0, code.0, value)
+}
+
+fn key(code: KeyCode, value: i32) -> InputEvent {
+    InputEvent::new(EventType::KEY.0, code.code(), value)
+}
+
+fn syn() -> InputEvent {
+    InputEvent::new(
+        EventType::SYNCHRONIZATION.0,
+        SynchronizationCode::SYN_REPORT.0,
+        0,
+    )
+}
+
+fn pressed(x: f32, y: f32) -> WindowEvent {
+    WindowEvent::PointerPressed {
+        position: LogicalPosition { x, y },
+        button: PointerEventButton::Left,
+    }
+}
+
+fn moved(x: f32, y: f32) -> WindowEvent {
+    WindowEvent::PointerMoved {
+        position: LogicalPosition { x, y },
+    }
+}
+
+fn released(x: f32, y: f32) -> WindowEvent {
+    WindowEvent::PointerReleased {
+        position: LogicalPosition { x, y },
+        button: PointerEventButton::Left,
+    }
+}
+
+fn convert(events: Vec<InputEvent>, config: Config) -> Vec<WindowEvent> {
+    convert_events(&events, config)
+}
+
+#[test]
+fn test_press_move_release() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 122),
+        abs(AbsoluteAxisCode::ABS_Y, 13),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_Y, 14),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            moved(122.0, 13.0),
+            moved(122.0, 14.0),
+            released(122.0, 14.0),
+        ],
+        convert(events, Config::new(1.0))
+    );
+}
+
+#[test]
+fn test_scale_factor() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+    ];
+
+    assert_eq!(vec![pressed(60.0, 6.0)], convert(events, Config::new(2.0)));
+}
+
+#[test]
+fn test_invalid_scale_factor() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+    ];
+
+    for scale_factor in [0.0, -1.0, f32::NAN] {
+        assert_eq!(
+            vec![pressed(120.0, 12.0)],
+            convert(events.clone(), Config::new(scale_factor))
+        );
+        // Opening a device is refused before the device is looked up
+        assert!(matches!(
+            SlintEventsWrapper::new("/nonexistent/event0", scale_factor),
+            Err(Error::InvalidScaleFactor(_))
+        ));
+    }
+}
+
+#[test]
+fn test_idle_moves_suppressed() {
+    let resting = || InputEvent::new(EventType::MISC.0, MiscCode::MSC_TIMESTAMP.0, 0);
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 122),
+        syn(),
+        resting(),
+        syn(),
+        resting(),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 123),
+        syn(),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![pressed(120.0, 12.0), moved(122.0, 12.0), moved(123.0, 12.0)],
+        convert(events, Config::new(1.0).emit_idle_moves(false))
+    );
+}
+
+#[test]
+fn test_suppress_move_at_press() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        // Syncs at the press position
+        syn(),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 122),
+        syn(),
+        // Once moved, a return to the press position is reported
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            moved(122.0, 12.0),
+            moved(120.0, 12.0),
+            released(120.0, 12.0),
+        ],
+        convert(events, Config::new(1.0).suppress_move_at_press(true))
+    );
+}
+
+#[test]
+fn test_no_events_before_position() {
+    let events = vec![
+        syn(),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 0),
+        syn(),
+        // The press is reported once both axes are known, even at the axis minimum
+        abs(AbsoluteAxisCode::ABS_Y, 0),
+        syn(),
+    ];
+
+    assert_eq!(vec![pressed(0.0, 0.0)], convert(events, Config::new(1.0)));
+}
+
+#[test]
+fn test_event_collector() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 122),
+        syn(),
+    ];
+
+    // Pushing one event at a time matches the converter, including the queued release of a tap
+    let mut collector = EventCollector::new(Config::new(1.0));
+    let mut window_events = Vec::new();
+    for event in events.iter().copied() {
+        window_events.extend(collector.take_queued());
+        window_events.extend(collector.push(event));
+    }
+    window_events.extend(collector.take_queued());
+    assert_eq!(convert(events, Config::new(1.0)), window_events);
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            released(120.0, 12.0),
+            moved(122.0, 12.0)
+        ],
+        window_events
+    );
+}
+
+#[test]
+fn test_stick_pointer() {
+    let mut stick = StickPointer::new(
+        AbsoluteAxisCode::ABS_HAT0X,
+        AbsoluteAxisCode::ABS_HAT0Y,
+        (-1, 1),
+    );
+    stick.speed = 100.0;
+    stick.exponent = 1.0;
+    let config = Config::new(1.0)
+        .stick_pointer(Some(stick))
+        .initial_position(Some(LogicalPosition::new(10.0, 10.0)));
+    let mut collector = EventCollector::with_capabilities(config, screen_caps(320, 240));
+    let mut window_events = Vec::new();
+
+    // The stick does not move the pointer until it is deflected
+    assert_eq!(None, collector.stick_tick());
+    assert_eq!(None, collector.advance_stick(Duration::from_millis(100)));
+    window_events.extend(collector.push(abs(AbsoluteAxisCode::ABS_HAT0X, 1)));
+    window_events.extend(collector.push(syn()));
+    assert_eq!(Some(stick.tick), collector.stick_tick());
+    window_events.extend(collector.advance_stick(Duration::from_millis(100)));
+    window_events.extend(collector.advance_stick(Duration::from_millis(50)));
+
+    // Touch moves the same pointer, from which the stick continues
+    window_events.extend(collector.push(abs(AbsoluteAxisCode::ABS_X, 40)));
+    window_events.extend(collector.push(abs(AbsoluteAxisCode::ABS_Y, 50)));
+    window_events.extend(collector.push(syn()));
+    window_events.extend(collector.push(abs(AbsoluteAxisCode::ABS_HAT0X, 0)));
+    window_events.extend(collector.push(abs(AbsoluteAxisCode::ABS_HAT0Y, 1)));
+    window_events.extend(collector.push(syn()));
+    window_events.extend(collector.advance_stick(Duration::from_millis(100)));
+
+    // The pointer stops at the edge of the screen
+    window_events.extend(collector.push(abs(AbsoluteAxisCode::ABS_HAT0Y, -1)));
+    window_events.extend(collector.push(syn()));
+    window_events.extend(collector.advance_stick(Duration::from_secs(2)));
+
+    // Centering the stick stops the pointer
+    window_events.extend(collector.push(abs(AbsoluteAxisCode::ABS_HAT0Y, 0)));
+    assert_eq!(None, collector.stick_tick());
+    assert_eq!(None, collector.advance_stick(Duration::from_millis(100)));
+
+    assert_eq!(
+        vec![
+            moved(10.0, 10.0),
+            moved(20.0, 10.0),
+            moved(25.0, 10.0),
+            moved(40.0, 50.0),
+            moved(40.0, 50.0),
+            moved(40.0, 60.0),
+            moved(40.0, 60.0),
+            moved(40.0, 0.0),
+        ],
+        window_events
+    );
+}
+
+#[test]
+fn test_press_at_contact_position() {
+    // A pen reporting hover on ABS_X and ABS_Y, and contact on the multitouch axes
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 100),
+        abs(AbsoluteAxisCode::ABS_Y, 100),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 1),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 150),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 160),
+        abs(AbsoluteAxisCode::ABS_X, 101),
+        abs(AbsoluteAxisCode::ABS_Y, 102),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, -1),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+    let caps = Capabilities {
+        multitouch: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        vec![
+            moved(100.0, 100.0),
+            pressed(150.0, 160.0),
+            released(150.0, 160.0)
+        ],
+        SlintEventsConverter::with_capabilities(events.clone(), Config::new(1.0), caps)
+            .collect::<Vec<_>>()
+    );
+
+    // Without contact axes, the press is at the hover position
+    let events = events
+        .into_iter()
+        .filter(|event| {
+            !matches!(
+                event.destructure(),
+                EventSummary::AbsoluteAxis(
+                    _,
+                    AbsoluteAxisCode::ABS_MT_TRACKING_ID
+                        | AbsoluteAxisCode::ABS_MT_POSITION_X
+                        | AbsoluteAxisCode::ABS_MT_POSITION_Y,
+                    _
+                )
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            moved(100.0, 100.0),
+            pressed(101.0, 102.0),
+            released(101.0, 102.0)
+        ],
+        convert(events, Config::new(1.0))
+    );
+}
+
+#[test]
+fn test_current_position() {
+    let mut collector = EventCollector::new(Config::new(2.0).swap_axes(true));
+    assert_eq!((0, 0), collector.last_raw_position());
+    collector.push(abs(AbsoluteAxisCode::ABS_X, 120));
+    collector.push(abs(AbsoluteAxisCode::ABS_Y, 40));
+    // The position is tracked before the frame is reported
+    assert_eq!((120, 40), collector.last_raw_position());
+    assert_eq!(
+        LogicalPosition::new(20.0, 60.0),
+        collector.current_position()
+    );
+    collector.push(syn());
+    assert_eq!(
+        LogicalPosition::new(20.0, 60.0),
+        collector.current_position()
+    );
+}
+
+#[test]
+fn test_scale_factor_change_during_drag() {
+    let mut collector = EventCollector::new(Config::new(1.0));
+    let mut window_events = Vec::new();
+    window_events.extend(collector.push(abs(AbsoluteAxisCode::ABS_X, 100)));
+    window_events.extend(collector.push(abs(AbsoluteAxisCode::ABS_Y, 50)));
+    window_events.extend(collector.push(key(KeyCode::BTN_TOUCH, 1)));
+    window_events.extend(collector.push(syn()));
+    collector.set_config(Config::new(2.0));
+    assert_eq!(
+        LogicalPosition::new(50.0, 25.0),
+        collector.current_position()
+    );
+    window_events.extend(collector.push(abs(AbsoluteAxisCode::ABS_X, 120)));
+    window_events.extend(collector.push(syn()));
+    window_events.extend(collector.push(key(KeyCode::BTN_TOUCH, 0)));
+    window_events.extend(collector.push(syn()));
+
+    assert_eq!(
+        vec![
+            pressed(100.0, 50.0),
+            moved(60.0, 25.0),
+            released(60.0, 25.0),
+        ],
+        window_events
+    );
+}
+
+/// Push events to a collector, returning the window events produced
+fn push_all(collector: &mut EventCollector, events: Vec<InputEvent>) -> Vec<WindowEvent> {
+    events
+        .into_iter()
+        .filter_map(|event| collector.push(event))
+        .collect()
+}
+
+#[test]
+fn test_pause() {
+    let mut collector = EventCollector::new(Config::new(1.0));
+    let touch = |value| vec![key(KeyCode::BTN_TOUCH, value), syn()];
+    let move_to = |x| vec![abs(AbsoluteAxisCode::ABS_X, x), syn()];
+    let mut press = touch(1);
+    press.insert(0, abs(AbsoluteAxisCode::ABS_Y, 12));
+    press.insert(0, abs(AbsoluteAxisCode::ABS_X, 120));
+    assert_eq!(vec![pressed(120.0, 12.0)], push_all(&mut collector, press));
+
+    // Nothing is emitted while paused, but the contact is still followed
+    collector.pause();
+    assert!(collector.is_paused());
+    assert!(push_all(&mut collector, move_to(130)).is_empty());
+    assert!(push_all(&mut collector, touch(0)).is_empty());
+    // The release while paused is reported on resuming
+    assert_eq!(Some(released(130.0, 12.0)), collector.resume());
+    assert!(!collector.is_paused());
+
+    assert_eq!(
+        vec![pressed(130.0, 12.0)],
+        push_all(&mut collector, touch(1))
+    );
+    collector.pause();
+    assert!(push_all(&mut collector, move_to(140)).is_empty());
+    // A contact still pressed moves to its current position on resuming
+    assert_eq!(Some(moved(140.0, 12.0)), collector.resume());
+
+    assert_eq!(
+        vec![released(140.0, 12.0)],
+        push_all(&mut collector, touch(0))
+    );
+    collector.pause();
+    assert!(push_all(&mut collector, touch(1)).is_empty());
+    // A contact pressed while paused is pressed on resuming
+    assert_eq!(Some(pressed(140.0, 12.0)), collector.resume());
+    assert_eq!(None, collector.resume());
+}
+
+#[test]
+fn test_set_config_at_frame_boundary() {
+    let mut collector = EventCollector::new(Config::new(1.0));
+    let mut window_events = Vec::new();
+    window_events.extend(collector.push(abs(AbsoluteAxisCode::ABS_X, 120)));
+    // A change during a frame waits for the frame to end
+    collector.set_config(Config::new(1.0).offset(10.0, 0.0));
+    window_events.extend(collector.push(abs(AbsoluteAxisCode::ABS_Y, 12)));
+    window_events.extend(collector.push(syn()));
+    window_events.extend(collector.push(abs(AbsoluteAxisCode::ABS_X, 122)));
+    window_events.extend(collector.push(syn()));
+
+    assert_eq!(vec![moved(120.0, 12.0), moved(132.0, 12.0)], window_events);
+}
+
+#[test]
+fn test_flush_on_read() {
+    // A driver which never reports SYN_REPORT
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+    ];
+
+    assert_eq!(
+        Vec::<WindowEvent>::new(),
+        convert(events.clone(), Config::new(1.0))
+    );
+    assert_eq!(
+        vec![moved(120.0, 12.0)],
+        convert(events.clone(), Config::new(1.0).flush_on_read(true))
+    );
+    assert_eq!(
+        vec![MultiTouchEvent {
+            id: 0,
+            event: moved(120.0, 12.0),
+            pressure: None,
+        }],
+        SlintEventsConverter::new(events.clone(), Config::new(1.0).flush_on_read(true))
+            .into_multitouch()
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![ExtendedEvent::Window(moved(120.0, 12.0))],
+        SlintEventsConverter::new(events, Config::new(1.0).flush_on_read(true))
+            .into_extended()
+            .collect::<Vec<_>>()
+    );
+    // A read ending in SYN_REPORT is not flushed again
+    assert_eq!(
+        vec![moved(120.0, 12.0)],
+        convert(
+            vec![
+                abs(AbsoluteAxisCode::ABS_X, 120),
+                abs(AbsoluteAxisCode::ABS_Y, 12),
+                syn()
+            ],
+            Config::new(1.0).flush_on_read(true)
+        )
+    );
+}
+
+#[test]
+fn test_initial_position() {
+    let events = vec![
+        syn(),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![
+            moved(160.0, 120.0),
+            pressed(160.0, 120.0),
+            moved(60.0, 120.0)
+        ],
+        convert(
+            events,
+            Config::new(2.0).initial_position(Some(LogicalPosition { x: 160.0, y: 120.0 }))
+        )
+    );
+}
+
+#[test]
+fn test_tracking_id_contact() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 0),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 5),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 120),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 12),
+        syn(),
+        // A second finger does not move the pointer
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 1),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 6),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 300),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 200),
+        syn(),
+        // Lifting the second finger does not release the pointer
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, -1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 0),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, -1),
+        syn(),
+    ];
+    let caps = Capabilities {
+        contact_source: ContactSource::TrackingId,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            moved(120.0, 12.0),
+            moved(120.0, 12.0),
+            released(120.0, 12.0),
+        ],
+        SlintEventsConverter::with_capabilities(events, Config::new(1.0), caps).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_lift_unreported_slots() {
+    // A device which reports every contact in every frame, and never reports a tracking id of -1
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 0),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 5),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 120),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 12),
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 1),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 6),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 300),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 200),
+        syn(),
+        // The first finger lifts, leaving only the second
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 1),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 302),
+        syn(),
+        // The second finger lifts, leaving no slots
+        syn(),
+    ];
+    let caps = Capabilities {
+        contact_source: ContactSource::TrackingId,
+        multitouch: true,
+        ..Default::default()
+    };
+
+    let config = Config::new(1.0).lift_unreported_slots(true);
+    assert_eq!(
+        vec![
+            MultiTouchEvent {
+                id: 5,
+                event: pressed(120.0, 12.0),
+                pressure: None,
+            },
+            MultiTouchEvent {
+                id: 6,
+                event: pressed(300.0, 200.0),
+                pressure: None,
+            },
+            MultiTouchEvent {
+                id: 5,
+                event: released(120.0, 12.0),
+                pressure: None,
+            },
+            MultiTouchEvent {
+                id: 6,
+                event: moved(302.0, 200.0),
+                pressure: None,
+            },
+            MultiTouchEvent {
+                id: 6,
+                event: released(302.0, 200.0),
+                pressure: None,
+            },
+        ],
+        SlintEventsConverter::with_capabilities(events.clone(), config, caps)
+            .into_multitouch()
+            .collect::<Vec<_>>()
+    );
+
+    // The pointer follows the first finger, and is released when it lifts
+    let config = Config::new(1.0).lift_unreported_slots(true);
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            released(120.0, 12.0),
+            moved(120.0, 12.0)
+        ],
+        SlintEventsConverter::with_capabilities(events.clone(), config, caps).collect::<Vec<_>>()
+    );
+
+    // Without the workaround, the contacts are never lifted
+    assert_eq!(
+        vec![pressed(120.0, 12.0), moved(120.0, 12.0), moved(120.0, 12.0)],
+        SlintEventsConverter::with_capabilities(events, Config::new(1.0), caps).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_multitouch_ids() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 0),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 5),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 120),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 1),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 6),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 300),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 200),
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 0),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 122),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, -1),
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 1),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 201),
+        syn(),
+        // A new contact reusing slot 0 gets a new id
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 0),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 7),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 50),
+        syn(),
+    ];
+    let caps = Capabilities {
+        multitouch: true,
+        ..Default::default()
+    };
+    let contact = |id, event| MultiTouchEvent {
+        id,
+        event,
+        pressure: None,
+    };
+
+    assert_eq!(
+        vec![
+            contact(5, pressed(120.0, 12.0)),
+            contact(5, moved(122.0, 12.0)),
+            contact(6, pressed(300.0, 200.0)),
+            contact(5, released(122.0, 12.0)),
+            contact(6, moved(300.0, 201.0)),
+            contact(7, pressed(50.0, 12.0)),
+        ],
+        SlintEventsConverter::with_capabilities(events, Config::new(1.0), caps)
+            .into_multitouch()
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_min_contact_pressure() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 0),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 5),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 120),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 12),
+        abs(AbsoluteAxisCode::ABS_MT_PRESSURE, 80),
+        // A light contact is not pressed
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 1),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 6),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 300),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 200),
+        abs(AbsoluteAxisCode::ABS_MT_PRESSURE, 20),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 302),
+        syn(),
+        // It is pressed once it is firm enough, and stays pressed until it is lifted
+        abs(AbsoluteAxisCode::ABS_MT_PRESSURE, 60),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 304),
+        abs(AbsoluteAxisCode::ABS_MT_PRESSURE, 10),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, -1),
+        syn(),
+    ];
+    let caps = Capabilities {
+        multitouch: true,
+        ..Default::default()
+    };
+    let contact = |id, event, pressure| MultiTouchEvent {
+        id,
+        event,
+        pressure: Some(pressure),
+    };
+
+    assert_eq!(
+        vec![
+            contact(5, pressed(120.0, 12.0), 80),
+            contact(6, pressed(302.0, 200.0), 60),
+            contact(6, moved(304.0, 200.0), 10),
+            contact(6, released(304.0, 200.0), 10),
+        ],
+        SlintEventsConverter::with_capabilities(
+            events,
+            Config::new(1.0).min_contact_pressure(Some(50)),
+            caps
+        )
+        .into_multitouch()
+        .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_stylus_tilt() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        abs(AbsoluteAxisCode::ABS_PRESSURE, 300),
+        abs(AbsoluteAxisCode::ABS_TILT_X, -20),
+        abs(AbsoluteAxisCode::ABS_TILT_Y, 15),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+    ];
+    let stylus_caps = Capabilities {
+        tilt: true,
+        ..Default::default()
+    };
+
+    let one_contact = ExtendedEvent::Gesture(GestureEvent::ContactCountChanged { count: 1 });
+    assert_eq!(
+        vec![
+            ExtendedEvent::Stylus(StylusEvent {
+                event: pressed(120.0, 12.0),
+                tool: StylusTool::Unknown,
+                pressure: Some(300),
+                tilt_x: -20,
+                tilt_y: 15,
+            }),
+            one_contact.clone(),
+        ],
+        SlintEventsConverter::with_capabilities(events.clone(), Config::new(1.0), stylus_caps)
+            .into_extended()
+            .collect::<Vec<_>>()
+    );
+
+    // Without tilt axes, plain window events are reported
+    assert_eq!(
+        vec![ExtendedEvent::Window(pressed(120.0, 12.0)), one_contact],
+        SlintEventsConverter::new(events, Config::new(1.0))
+            .into_extended()
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_stylus_tool() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOOL_RUBBER, 1),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        // The eraser leaves range as it is lifted
+        key(KeyCode::BTN_TOOL_RUBBER, 0),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        key(KeyCode::BTN_TOOL_PEN, 1),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+    ];
+    let stylus = |event, tool| {
+        ExtendedEvent::Stylus(StylusEvent {
+            event,
+            tool,
+            pressure: None,
+            tilt_x: 0,
+            tilt_y: 0,
+        })
+    };
+    let count = |count| ExtendedEvent::Gesture(GestureEvent::ContactCountChanged { count });
+
+    assert_eq!(
+        vec![
+            stylus(pressed(120.0, 12.0), StylusTool::Eraser),
+            count(1),
+            stylus(released(120.0, 12.0), StylusTool::Eraser),
+            count(0),
+            stylus(pressed(120.0, 12.0), StylusTool::Pen),
+            count(1),
+        ],
+        SlintEventsConverter::new(events, Config::new(1.0))
+            .into_extended()
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_pen_proximity() {
+    let events = vec![
+        // The pen comes into range, hovering
+        key(KeyCode::BTN_TOOL_PEN, 1),
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 122),
+        syn(),
+        // Contact, a stroke, and lifting back to hover
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 130),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 140),
+        syn(),
+        // The pen leaves range
+        key(KeyCode::BTN_TOOL_PEN, 0),
+        syn(),
+        // The pen comes into range and makes contact within one frame, and likewise leaves
+        key(KeyCode::BTN_TOOL_PEN, 1),
+        key(KeyCode::BTN_TOUCH, 1),
+        abs(AbsoluteAxisCode::ABS_X, 150),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        key(KeyCode::BTN_TOOL_PEN, 0),
+        syn(),
+    ];
+    let expected = vec![
+        moved(120.0, 12.0),
+        moved(122.0, 12.0),
+        pressed(122.0, 12.0),
+        moved(130.0, 12.0),
+        released(130.0, 12.0),
+        moved(140.0, 12.0),
+        pressed(150.0, 12.0),
+        released(150.0, 12.0),
+    ];
+
+    let config = Config::new(1.0).emit_idle_moves(false);
+    assert_eq!(expected, convert(events.clone(), config.clone()));
+
+    // BTN_TOUCH takes precedence when the pen's tool key is also a contact key
+    let config = config.contact_keys([KeyCode::BTN_TOUCH, KeyCode::BTN_TOOL_PEN]);
+    assert_eq!(expected, convert(events, config));
+}
+
+#[test]
+fn test_hover_distance() {
+    let events = vec![
+        key(KeyCode::BTN_TOOL_PEN, 1),
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        abs(AbsoluteAxisCode::ABS_DISTANCE, 30),
+        syn(),
+        // Moves are reported once the pen comes within range
+        abs(AbsoluteAxisCode::ABS_X, 122),
+        abs(AbsoluteAxisCode::ABS_DISTANCE, 10),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_DISTANCE, 0),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        // Contact is reported whatever the distance
+        abs(AbsoluteAxisCode::ABS_X, 130),
+        abs(AbsoluteAxisCode::ABS_DISTANCE, 40),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 140),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![
+            moved(122.0, 12.0),
+            pressed(122.0, 12.0),
+            moved(130.0, 12.0),
+            released(130.0, 12.0),
+        ],
+        convert(
+            events.clone(),
+            Config::new(1.0)
+                .emit_idle_moves(false)
+                .hover_distance(Some(20))
+        )
+    );
+    // Without a hover distance, all hover moves are reported
+    assert_eq!(
+        vec![
+            moved(120.0, 12.0),
+            moved(122.0, 12.0),
+            pressed(122.0, 12.0),
+            moved(130.0, 12.0),
+            released(130.0, 12.0),
+            moved(140.0, 12.0),
+        ],
+        convert(events, Config::new(1.0).emit_idle_moves(false))
+    );
+}
+
+#[test]
+fn test_press_position_order_independent() {
+    // The press is reported at the coordinates from its own sync packet, whether the key or the
+    // coordinates come first
+    let key_first = vec![
+        abs(AbsoluteAxisCode::ABS_X, 10),
+        abs(AbsoluteAxisCode::ABS_Y, 20),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 1),
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        syn(),
+    ];
+    let coordinates_first = vec![
+        abs(AbsoluteAxisCode::ABS_X, 10),
+        abs(AbsoluteAxisCode::ABS_Y, 20),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+    ];
+
+    for events in [key_first, coordinates_first] {
+        assert_eq!(
+            vec![moved(10.0, 20.0), pressed(120.0, 12.0)],
+            convert(events, Config::new(1.0))
+        );
+    }
+}
+
+#[test]
+fn test_tracking_id_press_position_order_independent() {
+    let id_first = vec![
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 1),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 120),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 12),
+        syn(),
+    ];
+    let coordinates_first = vec![
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 120),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 12),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 1),
+        syn(),
+    ];
+    let caps = Capabilities {
+        contact_source: ContactSource::TrackingId,
+        ..Default::default()
+    };
+
+    for events in [id_first, coordinates_first] {
+        assert_eq!(
+            vec![pressed(120.0, 12.0)],
+            SlintEventsConverter::with_capabilities(events, Config::new(1.0), caps)
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
+/// Capabilities of a single touch screen with the given axis maximums
+fn screen_caps(width: i32, height: i32) -> Capabilities {
+    Capabilities {
+        x_info: Some(AbsInfo::new(0, 0, width, 0, 0, 1)),
+        y_info: Some(AbsInfo::new(0, 0, height, 0, 0, 1)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_calibration_preview() {
+    let preview = |config| {
+        SlintEventsConverter::with_capabilities(Vec::new(), config, screen_caps(320, 240))
+            .calibration_preview()
+    };
+    let position = |x, y| LogicalPosition { x, y };
+
+    assert_eq!(
+        Some([
+            position(0.0, 0.0),
+            position(160.0, 0.0),
+            position(160.0, 120.0),
+            position(0.0, 120.0),
+        ]),
+        preview(Config::new(2.0))
+    );
+    assert_eq!(
+        Some([
+            position(0.0, 0.0),
+            position(0.0, 320.0),
+            position(240.0, 320.0),
+            position(240.0, 0.0),
+        ]),
+        preview(Config::new(1.0).swap_axes(true))
+    );
+    // Without axis ranges there are no corners
+    assert_eq!(
+        None,
+        SlintEventsConverter::new(Vec::new(), Config::new(1.0)).calibration_preview()
+    );
+}
+
+#[test]
+fn test_map_positions() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 130),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    let mirrored: Vec<_> = SlintEventsConverter::new(events, Config::new(1.0))
+        .map_positions(|position| LogicalPosition::new(320.0 - position.x, position.y))
+        .collect();
+    assert_eq!(
+        vec![
+            pressed(200.0, 12.0),
+            moved(190.0, 12.0),
+            released(190.0, 12.0),
+        ],
+        mirrored
+    );
+}
+
+#[test]
+fn test_release_drift() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 130),
+        syn(),
+        // The release drifts far from the last move
+        abs(AbsoluteAxisCode::ABS_X, 200),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        // The release drifts slightly from the press
+        abs(AbsoluteAxisCode::ABS_X, 125),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    let config = Config::new(1.0).release_drift(Some(Distance::Pixels(20.0)));
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            moved(130.0, 12.0),
+            released(130.0, 12.0),
+            pressed(120.0, 12.0),
+            released(125.0, 12.0),
+        ],
+        convert(events.clone(), config)
+    );
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            moved(130.0, 12.0),
+            released(200.0, 12.0),
+            pressed(120.0, 12.0),
+            released(125.0, 12.0),
+        ],
+        convert(events, Config::new(1.0))
+    );
+}
+
+#[test]
+fn test_screen_size_from() {
+    let path = std::env::temp_dir().join(format!("virtual_size_{}", std::process::id()));
+    std::fs::write(&path, "640,480\n").unwrap();
+    let config = Config::new(2.0).screen_size_from(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        Some([
+            LogicalPosition::new(0.0, 0.0),
+            LogicalPosition::new(320.0, 0.0),
+            LogicalPosition::new(320.0, 240.0),
+            LogicalPosition::new(0.0, 240.0),
+        ]),
+        SlintEventsConverter::with_capabilities(Vec::new(), config, screen_caps(1000, 1000))
+            .calibration_preview()
+    );
+    // A missing file requires the size to be set explicitly
+    assert!(matches!(
+        Config::new(1.0).screen_size_from(&path),
+        Err(Error::ScreenSizeUnavailable(missing)) if missing == path
+    ));
+}
+
+#[test]
+fn test_edge_scroll() {
+    let events = vec![
+        // A drag starting in the strip scrolls, even once it leaves the strip
+        abs(AbsoluteAxisCode::ABS_X, 310),
+        abs(AbsoluteAxisCode::ABS_Y, 100),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_Y, 130),
+        syn(),
+        // Moving only across the strip does not scroll
+        abs(AbsoluteAxisCode::ABS_X, 305),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 250),
+        abs(AbsoluteAxisCode::ABS_Y, 120),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        // A drag starting outside the strip is reported as usual
+        abs(AbsoluteAxisCode::ABS_X, 200),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_Y, 140),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![
+            WindowEvent::PointerScrolled {
+                position: LogicalPosition { x: 310.0, y: 130.0 },
+                delta_x: 0.0,
+                delta_y: 30.0,
+            },
+            WindowEvent::PointerScrolled {
+                position: LogicalPosition { x: 250.0, y: 120.0 },
+                delta_x: 0.0,
+                delta_y: -10.0,
+            },
+            pressed(200.0, 120.0),
+            moved(200.0, 140.0),
+            released(200.0, 140.0),
+        ],
+        SlintEventsConverter::with_capabilities(
+            events,
+            Config::new(1.0).edge_scroll(ScrollEdge::Right, 20.0),
+            screen_caps(320, 240)
+        )
+        .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_scroll_surface() {
+    let events = vec![
+        // A drag starting in the surface scrolls along both axes
+        abs(AbsoluteAxisCode::ABS_X, 110),
+        abs(AbsoluteAxisCode::ABS_Y, 60),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_Y, 90),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 105),
+        abs(AbsoluteAxisCode::ABS_Y, 80),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        // A drag starting outside the surface is reported as usual
+        abs(AbsoluteAxisCode::ABS_X, 200),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_Y, 100),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![
+            WindowEvent::PointerScrolled {
+                position: LogicalPosition { x: 110.0, y: 90.0 },
+                delta_x: 0.0,
+                delta_y: 30.0,
+            },
+            WindowEvent::PointerScrolled {
+                position: LogicalPosition { x: 105.0, y: 80.0 },
+                delta_x: -5.0,
+                delta_y: -10.0,
+            },
+            pressed(200.0, 80.0),
+            moved(200.0, 100.0),
+            released(200.0, 100.0),
+        ],
+        convert(
+            events,
+            Config::new(1.0).scroll_surface(100.0, 50.0, 20.0, 100.0)
+        )
+    );
+}
+
+#[test]
+fn test_smoothing() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 100),
+        abs(AbsoluteAxisCode::ABS_Y, 50),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 110),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 130),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        // A new contact is pressed where it lands, without lag
+        abs(AbsoluteAxisCode::ABS_X, 200),
+        abs(AbsoluteAxisCode::ABS_Y, 60),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_Y, 70),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![
+            pressed(100.0, 50.0),
+            moved(110.0, 50.0),
+            moved(110.0, 50.0),
+            moved(120.0, 50.0),
+            released(125.0, 50.0),
+            pressed(200.0, 60.0),
+            moved(200.0, 65.0),
+        ],
+        convert(events, Config::new(1.0).smoothing(Some(0.5)))
+    );
+}
+
+#[test]
+fn test_edge_margin() {
+    let events = vec![
+        // A touch on the bezel is ignored entirely
+        abs(AbsoluteAxisCode::ABS_X, 2),
+        abs(AbsoluteAxisCode::ABS_Y, 2),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        // A touch starting in the left margin is pressed once it enters the active area
+        abs(AbsoluteAxisCode::ABS_X, 5),
+        abs(AbsoluteAxisCode::ABS_Y, 100),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 20),
+        syn(),
+        // Moves into the right margin are ignored, but the release is reported
+        abs(AbsoluteAxisCode::ABS_X, 315),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+    let config = Config::new(1.0).edge_margin(10.0, 10.0, 10.0, 10.0);
+
+    assert_eq!(
+        vec![pressed(20.0, 100.0), released(315.0, 100.0)],
+        SlintEventsConverter::with_capabilities(events, config, screen_caps(320, 240))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_min_move_distance() {
+    let mut events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 100),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+    ];
+    for x in [103, 106, 109, 112, 115] {
+        events.extend([abs(AbsoluteAxisCode::ABS_X, x), syn()]);
+    }
+    events.extend([key(KeyCode::BTN_TOUCH, 0), syn()]);
+    // Hover moves are not filtered
+    events.extend([abs(AbsoluteAxisCode::ABS_X, 116), syn()]);
+
+    // Each move is measured from the last one reported
+    assert_eq!(
+        vec![
+            pressed(100.0, 12.0),
+            moved(106.0, 12.0),
+            moved(112.0, 12.0),
+            released(115.0, 12.0),
+            moved(116.0, 12.0),
+        ],
+        convert(
+            events,
+            Config::new(1.0).min_move_distance(Some(Distance::Pixels(5.0)))
+        )
+    );
+}
+
+#[test]
+fn test_tap_deadzone_millimeters() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 100),
+        abs(AbsoluteAxisCode::ABS_Y, 100),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 105),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 112),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 113),
+        syn(),
+    ];
+    let config = Config::new(1.0).tap_deadzone(Some(Distance::Millimeters {
+        mm: 1.0,
+        fallback_pixels: 5.0,
+    }));
+
+    // With 10 counts per mm, the deadzone is 10 counts
+    let caps = Capabilities {
+        x_info: Some(AbsInfo::new(0, 0, 320, 0, 0, 10)),
+        y_info: Some(AbsInfo::new(0, 0, 240, 0, 0, 10)),
+        ..Default::default()
+    };
+    assert_eq!(
+        vec![
+            pressed(100.0, 100.0),
+            moved(112.0, 100.0),
+            moved(113.0, 100.0)
+        ],
+        SlintEventsConverter::with_capabilities(events.clone(), config.clone(), caps)
+            .collect::<Vec<_>>()
+    );
+
+    // Without a reported resolution, the deadzone falls back to 5 pixels
+    let caps = Capabilities {
+        x_info: Some(AbsInfo::new(0, 0, 320, 0, 0, 0)),
+        y_info: Some(AbsInfo::new(0, 0, 240, 0, 0, 0)),
+        ..Default::default()
+    };
+    assert_eq!(
+        vec![
+            pressed(100.0, 100.0),
+            moved(112.0, 100.0),
+            moved(113.0, 100.0)
+        ],
+        SlintEventsConverter::with_capabilities(events.clone(), config.clone(), caps)
+            .collect::<Vec<_>>()
+    );
+    let config = config.tap_deadzone(Some(Distance::Millimeters {
+        mm: 1.0,
+        fallback_pixels: 4.0,
+    }));
+    assert_eq!(
+        vec![
+            pressed(100.0, 100.0),
+            moved(105.0, 100.0),
+            moved(112.0, 100.0),
+            moved(113.0, 100.0)
+        ],
+        SlintEventsConverter::with_capabilities(events, config, caps).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_contact_keys() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOOL_FINGER, 1),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        key(KeyCode::BTN_TOOL_FINGER, 0),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    // BTN_TOOL_FINGER alone
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            moved(120.0, 12.0),
+            released(120.0, 12.0),
+            moved(120.0, 12.0),
+        ],
+        convert(
+            events.clone(),
+            Config::new(1.0).contact_keys([KeyCode::BTN_TOOL_FINGER])
+        )
+    );
+
+    // Either key, with contact held until both are up, on a device which is not known to report
+    // BTN_TOUCH
+    let config = Config::new(1.0).contact_keys([KeyCode::BTN_TOUCH, KeyCode::BTN_TOOL_FINGER]);
+    let caps = Capabilities {
+        contact_source: ContactSource::Key(KeyCode::BTN_TOOL_FINGER),
+        ..Default::default()
+    };
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            moved(120.0, 12.0),
+            moved(120.0, 12.0),
+            released(120.0, 12.0),
+        ],
+        SlintEventsConverter::with_capabilities(events.clone(), config.clone(), caps)
+            .collect::<Vec<_>>()
+    );
+
+    // BTN_TOUCH takes precedence on a device which reports it
+    assert_eq!(
+        vec![
+            moved(120.0, 12.0),
+            pressed(120.0, 12.0),
+            moved(120.0, 12.0),
+            released(120.0, 12.0),
+        ],
+        convert(events, config)
+    );
+}
+
+#[test]
+fn test_single_touch_from_mt() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 0),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 5),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 100),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 100),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 1),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 6),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 200),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 200),
+        syn(),
+        // The first contact lifts while the second stays down
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 0),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, -1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 1),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 210),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, -1),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+    let caps = Capabilities {
+        multitouch: true,
+        ..Default::default()
+    };
+
+    // By default the pointer stays pressed, and moves to the second contact
+    assert_eq!(
+        vec![
+            pressed(100.0, 100.0),
+            moved(100.0, 100.0),
+            moved(210.0, 100.0),
+            released(210.0, 100.0),
+        ],
+        SlintEventsConverter::with_capabilities(
+            events.clone(),
+            Config::new(1.0).emit_idle_moves(false),
+            caps
+        )
+        .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![
+            pressed(100.0, 100.0),
+            moved(100.0, 100.0),
+            released(100.0, 100.0)
+        ],
+        SlintEventsConverter::with_capabilities(
+            events,
+            Config::new(1.0)
+                .emit_idle_moves(false)
+                .single_touch_from_mt(true),
+            caps
+        )
+        .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_contact_count_changed() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 0),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 5),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 120),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        // Two contacts arrive within one frame
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 1),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 6),
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 2),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 7),
+        syn(),
+        // Movement alone does not change the count
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 150),
+        syn(),
+        // One contact lifts while another lands
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, -1),
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 3),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 8),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 0),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, -1),
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 1),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, -1),
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 3),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, -1),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+    let caps = Capabilities {
+        multitouch: true,
+        ..Default::default()
+    };
+    let count = |count| ExtendedEvent::Gesture(GestureEvent::ContactCountChanged { count });
+
+    assert_eq!(
+        vec![
+            ExtendedEvent::Window(pressed(120.0, 12.0)),
+            count(1),
+            ExtendedEvent::Window(moved(120.0, 12.0)),
+            count(3),
+            ExtendedEvent::Window(released(120.0, 12.0)),
+            count(0),
+        ],
+        SlintEventsConverter::with_capabilities(
+            events,
+            Config::new(1.0).emit_idle_moves(false),
+            caps
+        )
+        .into_extended()
+        .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_tap_grid() {
+    let events = vec![
+        // A tap in row 0, column 2
+        abs(AbsoluteAxisCode::ABS_X, 250),
+        abs(AbsoluteAxisCode::ABS_Y, 50),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        // A drag from one cell into another
+        abs(AbsoluteAxisCode::ABS_X, 50),
+        abs(AbsoluteAxisCode::ABS_Y, 150),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 150),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        // A tap outside the grid
+        abs(AbsoluteAxisCode::ABS_X, 350),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+    let count = |count| ExtendedEvent::Gesture(GestureEvent::ContactCountChanged { count });
+
+    assert_eq!(
+        vec![
+            ExtendedEvent::Window(pressed(250.0, 50.0)),
+            count(1),
+            ExtendedEvent::Window(released(250.0, 50.0)),
+            count(0),
+            ExtendedEvent::Gesture(GestureEvent::CellTapped { row: 0, col: 2 }),
+            ExtendedEvent::Window(pressed(50.0, 150.0)),
+            count(1),
+            ExtendedEvent::Window(released(150.0, 150.0)),
+            count(0),
+            ExtendedEvent::Window(pressed(350.0, 150.0)),
+            count(1),
+            ExtendedEvent::Window(released(350.0, 150.0)),
+            count(0),
+        ],
+        SlintEventsConverter::new(events, Config::new(1.0).tap_grid(2, 3, 300.0, 200.0))
+            .into_extended()
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_ghost_filter() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 100),
+        abs(AbsoluteAxisCode::ABS_Y, 100),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 102),
+        syn(),
+        // A single outlier, followed by a return near the previous position
+        abs(AbsoluteAxisCode::ABS_X, 300),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 104),
+        syn(),
+        // A real jump, confirmed by the next sample
+        abs(AbsoluteAxisCode::ABS_X, 300),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 302),
+        syn(),
+        // An outlier which is never confirmed before release
+        abs(AbsoluteAxisCode::ABS_X, 30),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![
+            pressed(100.0, 100.0),
+            moved(102.0, 100.0),
+            moved(104.0, 100.0),
+            moved(302.0, 100.0),
+            released(302.0, 100.0),
+        ],
+        convert(
+            events,
+            Config::new(1.0).ghost_filter(Some(Distance::Pixels(50.0)))
+        )
+    );
+}
+
+#[test]
+fn test_metrics() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 100),
+        abs(AbsoluteAxisCode::ABS_Y, 100),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        // An outlier, and one which is never confirmed before release
+        abs(AbsoluteAxisCode::ABS_X, 300),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 102),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 30),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    let mut converter = SlintEventsConverter::new(
+        events,
+        Config::new(1.0).ghost_filter(Some(Distance::Pixels(50.0))),
+    );
+    assert_eq!(3, converter.by_ref().count());
+    assert_eq!(
+        Metrics {
+            raw_events: 12,
+            window_events: 3,
+            moves_coalesced: 0,
+            outliers_dropped: 2,
+        },
+        converter.metrics()
+    );
+}
+
+#[test]
+fn test_rounding() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 100),
+        abs(AbsoluteAxisCode::ABS_Y, 50),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![pressed(66.666664, 33.333332)],
+        convert(events.clone(), Config::new(1.5))
+    );
+    assert_eq!(
+        vec![pressed(67.0, 33.0)],
+        convert(events.clone(), Config::new(1.5).rounding(Rounding::Nearest))
+    );
+    assert_eq!(
+        vec![pressed(66.0, 33.0)],
+        convert(events, Config::new(1.5).rounding(Rounding::Floor))
+    );
+}
+
+#[test]
+fn test_snap_to_grid() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 99),
+        abs(AbsoluteAxisCode::ABS_Y, 54),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 111),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![
+            pressed(96.0, 50.0),
+            moved(112.0, 50.0),
+            released(112.0, 50.0)
+        ],
+        convert(events.clone(), Config::new(1.0).snap_to_grid(8.0, 10.0))
+    );
+    // Snapping applies after the offset
+    assert_eq!(
+        vec![
+            pressed(104.0, 60.0),
+            moved(112.0, 60.0),
+            released(112.0, 60.0)
+        ],
+        convert(
+            events.clone(),
+            Config::new(1.0).offset(4.0, 3.0).snap_to_grid(8.0, 10.0)
+        )
+    );
+    // An invalid step is ignored
+    assert_eq!(
+        vec![
+            pressed(99.0, 54.0),
+            moved(111.0, 54.0),
+            released(111.0, 54.0)
+        ],
+        convert(events, Config::new(1.0).snap_to_grid(0.0, 10.0))
+    );
+}
+
+#[test]
+fn test_invert_touch_polarity() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![pressed(120.0, 12.0), released(120.0, 12.0)],
+        convert(events, Config::new(1.0).invert_touch_polarity(true))
+    );
+}
+
+#[test]
+fn test_tap_within_one_frame() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 130),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            released(120.0, 12.0),
+            pressed(130.0, 12.0)
+        ],
+        convert(events, Config::new(1.0))
+    );
+}
+
+#[test]
+fn test_position_handler() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 500),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 120),
+        abs(AbsoluteAxisCode::ABS_Z, 24),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_Z, 28),
+        syn(),
+    ];
+
+    let config = Config::new(1.0).position_handler(|event, position| {
+        if let EventSummary::AbsoluteAxis(_, code, value) = *event {
+            match code {
+                AbsoluteAxisCode::ABS_MT_POSITION_X => position.0 = value,
+                AbsoluteAxisCode::ABS_Z => position.1 = value / 2,
+                _ => (),
+            }
+        }
+    });
+    assert_eq!(
+        vec![pressed(120.0, 12.0), moved(120.0, 14.0)],
+        convert(events, config)
+    );
+}
+
+#[test]
+fn test_position_axes() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 500),
+        abs(AbsoluteAxisCode::ABS_RX, 120),
+        abs(AbsoluteAxisCode::ABS_RY, 40),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_RY, 44),
+        syn(),
+    ];
+
+    let config = Config::new(1.0)
+        .x_axis(AbsoluteAxisCode::ABS_RX)
+        .y_axis(AbsoluteAxisCode::ABS_RY);
+    assert_eq!(
+        vec![pressed(120.0, 40.0), moved(120.0, 44.0)],
+        convert(events, config)
+    );
+}
+
+#[test]
+fn test_modifier_button() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        // Held as the contact is pressed, and released before the contact
+        key(KeyCode::BTN_RIGHT, 1),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        key(KeyCode::BTN_RIGHT, 0),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    let right_pressed = WindowEvent::PointerPressed {
+        position: LogicalPosition { x: 120.0, y: 12.0 },
+        button: PointerEventButton::Right,
+    };
+    let right_released = WindowEvent::PointerReleased {
+        position: LogicalPosition { x: 120.0, y: 12.0 },
+        button: PointerEventButton::Right,
+    };
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            released(120.0, 12.0),
+            right_pressed,
+            moved(120.0, 12.0),
+            right_released,
+        ],
+        convert(
+            events,
+            Config::new(1.0).modifier_button(KeyCode::BTN_RIGHT, PointerEventButton::Right)
+        )
+    );
+}
+
+#[test]
+fn test_default_button() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 130),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    let config = Config::new(1.0)
+        .default_button(PointerEventButton::Right)
+        .drag_events(true);
+    assert_eq!(
+        vec![
+            ExtendedEvent::Window(WindowEvent::PointerPressed {
+                position: LogicalPosition { x: 120.0, y: 12.0 },
+                button: PointerEventButton::Right,
+            }),
+            ExtendedEvent::Drag(DragEvent {
+                position: LogicalPosition { x: 130.0, y: 12.0 },
+                button: PointerEventButton::Right,
+            }),
+            ExtendedEvent::Window(WindowEvent::PointerReleased {
+                position: LogicalPosition { x: 130.0, y: 12.0 },
+                button: PointerEventButton::Right,
+            }),
+        ],
+        SlintEventsConverter::new(events, config)
+            .into_extended()
+            .filter(|event| !matches!(event, ExtendedEvent::Gesture(_)))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_drag_events() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_RIGHT, 1),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 130),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        // Moves without contact are not drags
+        abs(AbsoluteAxisCode::ABS_X, 140),
+        syn(),
+    ];
+    let count = |count| ExtendedEvent::Gesture(GestureEvent::ContactCountChanged { count });
+
+    assert_eq!(
+        vec![
+            ExtendedEvent::Window(WindowEvent::PointerPressed {
+                position: LogicalPosition { x: 120.0, y: 12.0 },
+                button: PointerEventButton::Right,
+            }),
+            count(1),
+            ExtendedEvent::Drag(DragEvent {
+                position: LogicalPosition { x: 130.0, y: 12.0 },
+                button: PointerEventButton::Right,
+            }),
+            ExtendedEvent::Window(WindowEvent::PointerReleased {
+                position: LogicalPosition { x: 130.0, y: 12.0 },
+                button: PointerEventButton::Right,
+            }),
+            count(0),
+            ExtendedEvent::Window(moved(140.0, 12.0)),
+        ],
+        SlintEventsConverter::new(
+            events,
+            Config::new(1.0)
+                .modifier_button(KeyCode::BTN_RIGHT, PointerEventButton::Right)
+                .drag_events(true)
+        )
+        .into_extended()
+        .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_modifier_change_during_contact() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        // Pressed after the contact, so the contact remains a left button press
+        key(KeyCode::BTN_RIGHT, 1),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        // A release with nothing pressed is not reported
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            moved(120.0, 12.0),
+            released(120.0, 12.0),
+            moved(120.0, 12.0),
+        ],
+        convert(
+            events,
+            Config::new(1.0).modifier_button(KeyCode::BTN_RIGHT, PointerEventButton::Right)
+        )
+    );
+}
+
+#[test]
+fn test_type_a_multitouch() {
+    let mt_report = || {
+        InputEvent::new(
+            EventType::SYNCHRONIZATION.0,
+            SynchronizationCode::SYN_MT_REPORT.0,
+            0,
+        )
+    };
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 120),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 12),
+        mt_report(),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 122),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 12),
+        mt_report(),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 300),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 200),
+        mt_report(),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 124),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 12),
+        mt_report(),
+        syn(),
+        // No contacts
+        mt_report(),
+        syn(),
+    ];
+    let caps = Capabilities {
+        contact_source: ContactSource::TrackingId,
+        type_a: true,
+        ..Default::default()
+    };
+    let contact = |id, event| MultiTouchEvent {
+        id,
+        event,
+        pressure: None,
+    };
+
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            moved(122.0, 12.0),
+            moved(124.0, 12.0),
+            released(124.0, 12.0),
+        ],
+        SlintEventsConverter::with_capabilities(events.clone(), Config::new(1.0), caps)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![
+            contact(0, pressed(120.0, 12.0)),
+            contact(0, moved(122.0, 12.0)),
+            contact(1, pressed(300.0, 200.0)),
+            contact(0, moved(124.0, 12.0)),
+            contact(1, released(300.0, 200.0)),
+            contact(0, released(124.0, 12.0)),
+        ],
+        SlintEventsConverter::with_capabilities(events, Config::new(1.0), caps)
+            .into_multitouch()
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_frame_end_codes() {
+    let mt_report = || {
+        InputEvent::new(
+            EventType::SYNCHRONIZATION.0,
+            SynchronizationCode::SYN_MT_REPORT.0,
+            0,
+        )
+    };
+    // A type-A stream which never reports SYN_REPORT
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 120),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 12),
+        mt_report(),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 122),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 12),
+        mt_report(),
+        // No contacts
+        mt_report(),
+    ];
+    let caps = Capabilities {
+        contact_source: ContactSource::TrackingId,
+        type_a: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        Vec::<WindowEvent>::new(),
+        SlintEventsConverter::with_capabilities(events.clone(), Config::new(1.0), caps)
+            .collect::<Vec<_>>()
+    );
+    let config = Config::new(1.0).frame_end_codes([
+        SynchronizationCode::SYN_REPORT,
+        SynchronizationCode::SYN_MT_REPORT,
+    ]);
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            moved(122.0, 12.0),
+            released(122.0, 12.0),
+        ],
+        SlintEventsConverter::with_capabilities(events, config, caps).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_contact_as_key() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 122),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    let text: slint::SharedString = Key::Return.into();
+    assert_eq!(
+        vec![
+            WindowEvent::KeyPressed { text: text.clone() },
+            WindowEvent::KeyReleased { text: text.clone() },
+        ],
+        convert(events, Config::new(1.0).contact_as_key(Some(text)))
+    );
+}
+
+#[test]
+fn test_repeated_down() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        // Up and down again within one frame, while still pressed
+        key(KeyCode::BTN_TOUCH, 0),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            moved(120.0, 12.0),
+            released(120.0, 12.0),
+            pressed(120.0, 12.0),
+            released(120.0, 12.0),
+        ],
+        convert(events, Config::new(1.0))
+    );
+}
+
+#[test]
+fn test_missed_lift() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 0),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 5),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 120),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 12),
+        syn(),
+        // The slot is reused for a new contact without the first being lifted
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 6),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 300),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 200),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, -1),
+        syn(),
+    ];
+    let caps = Capabilities {
+        contact_source: ContactSource::TrackingId,
+        multitouch: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            released(120.0, 12.0),
+            pressed(300.0, 200.0),
+            released(300.0, 200.0),
+        ],
+        SlintEventsConverter::with_capabilities(events, Config::new(1.0), caps).collect::<Vec<_>>()
+    );
+
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 40),
+        abs(AbsoluteAxisCode::ABS_Y, 50),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        // Lifted and touched again elsewhere within one frame
+        key(KeyCode::BTN_TOUCH, 0),
+        key(KeyCode::BTN_TOUCH, 1),
+        abs(AbsoluteAxisCode::ABS_X, 60),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![
+            pressed(40.0, 50.0),
+            released(40.0, 50.0),
+            pressed(60.0, 50.0),
+            released(60.0, 50.0),
+        ],
+        convert(events, Config::new(1.0))
+    );
+}
+
+#[test]
+fn test_missed_lift_with_tap() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 40),
+        abs(AbsoluteAxisCode::ABS_Y, 50),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        // Lifted, then tapped elsewhere, within one frame
+        key(KeyCode::BTN_TOUCH, 0),
+        key(KeyCode::BTN_TOUCH, 1),
+        abs(AbsoluteAxisCode::ABS_X, 60),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+    let expected = vec![
+        pressed(40.0, 50.0),
+        released(40.0, 50.0),
+        pressed(60.0, 50.0),
+        released(60.0, 50.0),
+    ];
+
+    assert_eq!(expected, convert(events.clone(), Config::new(1.0)));
+
+    // The release is returned by the push, and both halves of the tap are queued behind it
+    let mut collector = EventCollector::new(Config::new(1.0));
+    let mut window_events = Vec::new();
+    for event in events {
+        window_events.extend(std::iter::from_fn(|| collector.take_queued()));
+        window_events.extend(collector.push(event));
+    }
+    window_events.extend(std::iter::from_fn(|| collector.take_queued()));
+    assert_eq!(expected, window_events);
+}
+
+#[test]
+fn test_repeated_up() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![
+            pressed(120.0, 12.0),
+            released(120.0, 12.0),
+            moved(120.0, 12.0),
+        ],
+        convert(events, Config::new(1.0))
+    );
+}
+
+#[test]
+fn test_park_position() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+    ];
+
+    let park = LogicalPosition { x: -1.0, y: -1.0 };
+    assert_eq!(
+        vec![moved(-1.0, -1.0), pressed(120.0, 12.0)],
+        convert(events, Config::new(1.0).park_position(Some(park)))
+    );
+    assert_eq!(
+        vec![moved(-1.0, -1.0)],
+        convert(Vec::new(), Config::new(1.0).park_position(Some(park)))
+    );
+}
+
+#[test]
+fn test_capture_on_press() {
+    let events = vec![
+        // A touch on the bezel is still ignored
+        abs(AbsoluteAxisCode::ABS_X, 2),
+        abs(AbsoluteAxisCode::ABS_Y, 2),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        // Once pressed, moves into the right margin are reported
+        abs(AbsoluteAxisCode::ABS_X, 20),
+        abs(AbsoluteAxisCode::ABS_Y, 100),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 315),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+    ];
+    let config = Config::new(1.0)
+        .edge_margin(10.0, 10.0, 10.0, 10.0)
+        .capture_on_press(true);
+
+    assert_eq!(
+        vec![
+            pressed(20.0, 100.0),
+            moved(315.0, 100.0),
+            released(315.0, 100.0)
+        ],
+        SlintEventsConverter::with_capabilities(events, config, screen_caps(320, 240))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_sync_codes() {
+    let sync = |code: SynchronizationCode| InputEvent::new(EventType::SYNCHRONIZATION.0, code.0, 0);
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        // SYN_CONFIG does not end a frame
+        abs(AbsoluteAxisCode::ABS_X, 122),
+        sync(SynchronizationCode::SYN_CONFIG),
+        abs(AbsoluteAxisCode::ABS_Y, 13),
+        syn(),
+        // Events following SYN_DROPPED are discarded up to the next SYN_REPORT
+        sync(SynchronizationCode::SYN_DROPPED),
+        abs(AbsoluteAxisCode::ABS_X, 500),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 124),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![pressed(120.0, 12.0), moved(122.0, 13.0), moved(124.0, 13.0)],
+        convert(events, Config::new(1.0))
+    );
+}
+
+#[test]
+fn test_physical_output() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 121),
+        abs(AbsoluteAxisCode::ABS_Y, 13),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![pressed(60.5, 6.5)],
+        convert(events.clone(), Config::new(2.0))
+    );
+    assert_eq!(
+        vec![pressed(121.0, 13.0)],
+        convert(events.clone(), Config::new(2.0).physical_output(true))
+    );
+
+    // A normalized size is given in logical pixels
+    let config =
+        Config::new(2.0)
+            .physical_output(true)
+            .coordinate_mode(CoordinateMode::Normalized {
+                width: 1.0,
+                height: 1.0,
+            });
+    assert_eq!(
+        vec![pressed(0.5, 0.25)],
+        SlintEventsConverter::with_capabilities(
+            vec![
+                abs(AbsoluteAxisCode::ABS_X, 160),
+                abs(AbsoluteAxisCode::ABS_Y, 60),
+                key(KeyCode::BTN_TOUCH, 1),
+                syn(),
+            ],
+            config,
+            screen_caps(640, 480)
+        )
+        .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_offset() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+    ];
+
+    assert_eq!(
+        vec![pressed(40.0, -4.0)],
+        convert(events, Config::new(1.5).offset(-40.0, -12.0))
+    );
+}
+
+#[test]
+fn test_into_frames() {
+    let events = vec![
+        // Nothing is reported until the position is known
+        key(KeyCode::BTN_TOUCH, 1),
+        syn(),
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        syn(),
+        // Trailing events without a SYN_REPORT are still returned
+        abs(AbsoluteAxisCode::ABS_X, 130),
+    ];
+
+    let frames: Vec<_> = SlintEventsConverter::new(events, Config::new(1.0).emit_idle_moves(false))
+        .into_frames()
+        .collect();
+    assert_eq!(4, frames.len());
+
+    let (raw, event) = &frames[0];
+    assert!(matches!(
+        raw.as_slice(),
+        [
+            EventSummary::Key(_, KeyCode::BTN_TOUCH, 1),
+            EventSummary::Synchronization(_, SynchronizationCode::SYN_REPORT, _),
+        ]
+    ));
+    assert_eq!(&None, event);
+
+    let (raw, event) = &frames[1];
+    assert!(matches!(
+        raw.as_slice(),
+        [
+            EventSummary::AbsoluteAxis(_, AbsoluteAxisCode::ABS_X, 120),
+            EventSummary::AbsoluteAxis(_, AbsoluteAxisCode::ABS_Y, 12),
+            EventSummary::Synchronization(_, SynchronizationCode::SYN_REPORT, _),
+        ]
+    ));
+    assert_eq!(&Some(pressed(120.0, 12.0)), event);
+
+    let (raw, event) = &frames[2];
+    assert_eq!(2, raw.len());
+    assert_eq!(&Some(released(120.0, 12.0)), event);
+
+    let (raw, event) = &frames[3];
+    assert!(matches!(
+        raw.as_slice(),
+        [EventSummary::AbsoluteAxis(_, AbsoluteAxisCode::ABS_X, 130)]
+    ));
+    assert_eq!(&None, event);
+}
+
+#[test]
+fn test_modifiers() {
+    let events = vec![
+        abs(AbsoluteAxisCode::ABS_X, 120),
+        abs(AbsoluteAxisCode::ABS_Y, 12),
+        key(KeyCode::KEY_LEFTSHIFT, 1),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 1),
+        // Autorepeat of a held modifier is ignored
+        key(KeyCode::KEY_LEFTSHIFT, 2),
+        syn(),
+        key(KeyCode::BTN_TOUCH, 0),
+        key(KeyCode::KEY_LEFTSHIFT, 0),
+        syn(),
+    ];
+
+    let mut collector = EventCollector::new(Config::new(1.0).emit_idle_moves(false));
+    push_all(&mut collector, events[..4].to_vec());
+    assert_eq!(
+        Modifiers {
+            shift: true,
+            ..Default::default()
+        },
+        collector.current_modifiers()
+    );
+    collector.push(key(KeyCode::KEY_RIGHTCTRL, 1));
+    assert_eq!(
+        Modifiers {
+            shift: true,
+            control: true,
+            ..Default::default()
+        },
+        collector.current_modifiers()
+    );
+    collector.push(key(KeyCode::KEY_LEFTSHIFT, 0));
+    collector.push(key(KeyCode::KEY_RIGHTCTRL, 0));
+    assert_eq!(Modifiers::default(), collector.current_modifiers());
+
+    // Modifiers are not reported by default
+    assert_eq!(
+        vec![
+            moved(120.0, 12.0),
+            pressed(120.0, 12.0),
+            released(120.0, 12.0)
+        ],
+        convert(events.clone(), Config::new(1.0).emit_idle_moves(false))
+    );
+    let shift: slint::SharedString = Key::Shift.into();
+    assert_eq!(
+        vec![
+            WindowEvent::KeyPressed {
+                text: shift.clone()
+            },
+            moved(120.0, 12.0),
+            pressed(120.0, 12.0),
+            WindowEvent::KeyReleased { text: shift },
+            released(120.0, 12.0),
+        ],
+        convert(
+            events,
+            Config::new(1.0)
+                .emit_idle_moves(false)
+                .forward_modifiers(true)
+        )
+    );
+}
@@ -0,0 +1,102 @@
+use evdev::{AbsoluteAxisCode, EventType, InputEvent, KeyCode, SynchronizationCode};
+use slint::{
+    LogicalPosition,
+    platform::{PointerEventButton, WindowEvent},
+};
+use slint_evdev_input::{Config, MockEventSource, SlintEventsConverter, WindowEventSource};
+
+/// Count the presses fetched from a source, as application code might
+fn count_presses(source: &mut impl WindowEventSource) -> usize {
+    let mut presses = 0;
+    let mut buf = Vec::new();
+    loop {
+        source.fetch_events_into(&mut buf).unwrap();
+        if buf.is_empty() {
+            return presses;
+        }
+        presses += buf
+            .iter()
+            .filter(|event| matches!(event, WindowEvent::PointerPressed { .. }))
+            .count();
+    }
+}
+
+#[test]
+fn test_mock_event_source() {
+    let mut source = MockEventSource::new(Config::new(2.0));
+    source.push_events([WindowEvent::PointerPressed {
+        position: LogicalPosition { x: 1.0, y: 2.0 },
+        button: PointerEventButton::Left,
+    }]);
+    source.push_raw([
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+        InputEvent::new(
+            EventType::SYNCHRONIZATION.0,
+            SynchronizationCode::SYN_REPORT.0,
+            0,
+        ),
+    ]);
+
+    assert_eq!(
+        vec![WindowEvent::PointerPressed {
+            position: LogicalPosition { x: 1.0, y: 2.0 },
+            button: PointerEventButton::Left,
+        }],
+        source.fetch_window_events().unwrap()
+    );
+    // Raw events are converted with the configured scale factor
+    assert_eq!(
+        vec![WindowEvent::PointerPressed {
+            position: LogicalPosition { x: 60.0, y: 6.0 },
+            button: PointerEventButton::Left,
+        }],
+        source.fetch_window_events().unwrap()
+    );
+    assert!(source.is_empty());
+
+    source.push_events([
+        WindowEvent::PointerPressed {
+            position: LogicalPosition { x: 1.0, y: 2.0 },
+            button: PointerEventButton::Left,
+        },
+        WindowEvent::PointerPressed {
+            position: LogicalPosition { x: 3.0, y: 4.0 },
+            button: PointerEventButton::Left,
+        },
+    ]);
+    assert_eq!(2, count_presses(&mut source));
+}
+
+/// Collect every event from a source, as application code might
+fn collect_all(source: impl Iterator<Item = WindowEvent>) -> Vec<WindowEvent> {
+    source.collect()
+}
+
+#[test]
+fn test_event_iterators() {
+    let raw = [
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+        InputEvent::new(
+            EventType::SYNCHRONIZATION.0,
+            SynchronizationCode::SYN_REPORT.0,
+            0,
+        ),
+    ];
+    let expected = vec![WindowEvent::PointerPressed {
+        position: LogicalPosition { x: 120.0, y: 12.0 },
+        button: PointerEventButton::Left,
+    }];
+
+    let mut converter = SlintEventsConverter::new(raw, Config::default());
+    assert_eq!(expected, collect_all(&mut converter));
+
+    let mut mock = MockEventSource::new(Config::default());
+    mock.push_events([]);
+    mock.push_raw(raw);
+    assert_eq!(expected, collect_all(&mut mock));
+    assert!(mock.is_empty());
+}
@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use evdev::{
+    AbsInfo, AbsoluteAxisCode, EventType, InputEvent, UinputAbsSetup, uinput::VirtualDevice,
+};
+use slint_evdev_input::SlintEventsWrapper;
+
+const WIDTH: i32 = 320;
+const HEIGHT: i32 = 240;
+use slint::{
+    LogicalPosition,
+    platform::{PointerEventButton, WindowEvent},
+};
+
+fn abs(code: AbsoluteAxisCode, value: i32) -> InputEvent {
+    InputEvent::new(EventType::ABSOLUTE.0, code.0, value)
+}
+
+#[test]
+fn test_tracking_id_without_btn_touch() {
+    // A type-B multitouch device which never reports BTN_TOUCH
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_tracking_id_without_btn_touch")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_MT_SLOT,
+            AbsInfo::new(0, 0, 9, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_MT_TRACKING_ID,
+            AbsInfo::new(0, -1, 65535, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_MT_POSITION_X,
+            AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_MT_POSITION_Y,
+            AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+        ))
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+
+    // It seems some time is required here for the device to be created and for udev rules to be
+    // applied
+    std::thread::sleep(Duration::from_millis(200));
+
+    println!("Opening {dev_path:?}");
+    let mut stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+
+    let mut slint_events = Vec::new();
+
+    // Read in a thread so we can timeout
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        loop {
+            for event in stream.fetch_events() {
+                sender.send(event).unwrap();
+            }
+        }
+    });
+
+    // First finger down at (120, 12)
+    vdev.emit(&[
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 0),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 5),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 120),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 12),
+    ])
+    .unwrap();
+    // Second finger down elsewhere, first finger moves to (122, 13)
+    vdev.emit(&[
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 1),
+        abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, 6),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 300),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 200),
+        abs(AbsoluteAxisCode::ABS_MT_SLOT, 0),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_X, 122),
+        abs(AbsoluteAxisCode::ABS_MT_POSITION_Y, 13),
+    ])
+    .unwrap();
+    // First finger up
+    vdev.emit(&[abs(AbsoluteAxisCode::ABS_MT_TRACKING_ID, -1)])
+        .unwrap();
+
+    while let Ok(event) = receiver.recv_timeout(Duration::from_millis(50)) {
+        slint_events.push(event);
+    }
+
+    assert_eq!(
+        vec![
+            WindowEvent::PointerPressed {
+                position: LogicalPosition { x: 120.0, y: 12.0 },
+                button: PointerEventButton::Left
+            },
+            WindowEvent::PointerMoved {
+                position: LogicalPosition { x: 122.0, y: 13.0 }
+            },
+            WindowEvent::PointerReleased {
+                position: LogicalPosition { x: 122.0, y: 13.0 },
+                button: PointerEventButton::Left
+            },
+        ],
+        slint_events
+    );
+}
@@ -1,10 +1,10 @@
 use std::time::Duration;
 
 use evdev::{
-    AbsInfo, AbsoluteAxisCode, AttributeSet, EventType, InputEvent, KeyCode, UinputAbsSetup,
-    uinput::VirtualDevice,
+    AbsInfo, AbsoluteAxisCode, AttributeSet, EventType, InputEvent, KeyCode, MiscCode,
+    UinputAbsSetup, uinput::VirtualDevice,
 };
-use slint_evdev_input::SlintEventsWrapper;
+use slint_evdev_input::{Config, CoordinateMode, Error, SlintEventsWrapper, ThreadPriority};
 
 const WIDTH: i32 = 320;
 const HEIGHT: i32 = 240;
@@ -115,3 +115,862 @@ fn test_sync_events() {
         slint_events
     );
 }
+
+#[test]
+fn test_idle_moves_suppressed() {
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut msc = AttributeSet::<MiscCode>::new();
+    msc.insert(MiscCode::MSC_TIMESTAMP);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_idle_moves_suppressed")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .with_msc(&msc)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(200));
+
+    println!("Opening {dev_path:?}");
+    let mut stream =
+        SlintEventsWrapper::with_config(dev_path, Config::new(1.0).emit_idle_moves(false))
+            .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+
+    let mut slint_events = Vec::new();
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        loop {
+            for event in stream.fetch_events() {
+                sender.send(event).unwrap();
+            }
+        }
+    });
+
+    // Button down at (120, 12)
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    // Finger moves to (122, 13), then rests there while the driver keeps reporting. The kernel
+    // drops frames without changes, so each resting frame carries a new timestamp.
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 122),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 13),
+    ])
+    .unwrap();
+    for timestamp in 1..4 {
+        vdev.emit(&[InputEvent::new(
+            EventType::MISC.0,
+            MiscCode::MSC_TIMESTAMP.0,
+            timestamp,
+        )])
+        .unwrap();
+    }
+    // Button up
+    vdev.emit(&[InputEvent::new(
+        EventType::KEY.0,
+        KeyCode::BTN_TOUCH.code(),
+        0,
+    )])
+    .unwrap();
+
+    while let Ok(event) = receiver.recv_timeout(Duration::from_millis(50)) {
+        slint_events.push(event);
+    }
+
+    assert_eq!(
+        vec![
+            WindowEvent::PointerPressed {
+                position: LogicalPosition { x: 120.0, y: 12.0 },
+                button: PointerEventButton::Left
+            },
+            WindowEvent::PointerMoved {
+                position: LogicalPosition { x: 122.0, y: 13.0 }
+            },
+            WindowEvent::PointerReleased {
+                position: LogicalPosition { x: 122.0, y: 13.0 },
+                button: PointerEventButton::Left
+            },
+        ],
+        slint_events
+    );
+}
+
+#[test]
+fn test_fetch_events_timeout() {
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_fetch_events_timeout")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(200));
+
+    println!("Opening {dev_path:?}");
+    let mut stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+
+    // Nothing has been emitted, so this should time out with no events
+    let start = std::time::Instant::now();
+    let events: Vec<_> = stream
+        .fetch_events_timeout(Duration::from_millis(50))
+        .unwrap()
+        .collect();
+    assert!(events.is_empty());
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    // Button down at (120, 12)
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+
+    let events: Vec<_> = stream
+        .fetch_events_timeout(Duration::from_millis(50))
+        .unwrap()
+        .collect();
+    assert_eq!(
+        vec![WindowEvent::PointerPressed {
+            position: LogicalPosition { x: 120.0, y: 12.0 },
+            button: PointerEventButton::Left
+        }],
+        events
+    );
+}
+
+/// Create a single touch virtual device, and return it along with its device node path
+fn create_touch_device(name: &str) -> (VirtualDevice, std::path::PathBuf) {
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name(name)
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+
+    // It seems some time is required here for the device to be created and for udev rules to be
+    // applied
+    std::thread::sleep(Duration::from_millis(200));
+
+    (vdev, dev_path)
+}
+
+/// Read events from the wrapper in a thread, so that the test can timeout
+fn spawn_reader(mut stream: SlintEventsWrapper) -> std::sync::mpsc::Receiver<WindowEvent> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        loop {
+            for event in stream.fetch_events() {
+                sender.send(event).unwrap();
+            }
+        }
+    });
+    receiver
+}
+
+/// Collect received events until none arrive for 50ms
+fn collect_events(receiver: &std::sync::mpsc::Receiver<WindowEvent>) -> Vec<WindowEvent> {
+    let mut slint_events = Vec::new();
+    while let Ok(event) = receiver.recv_timeout(Duration::from_millis(50)) {
+        slint_events.push(event);
+    }
+    slint_events
+}
+
+#[test]
+fn test_normalized_coordinates() {
+    let (mut vdev, dev_path) = create_touch_device("test_normalized_coordinates");
+    let config = Config::new(2.0).coordinate_mode(CoordinateMode::Normalized {
+        width: 1.0,
+        height: 1.0,
+    });
+    let stream = SlintEventsWrapper::with_config(dev_path, config)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+    let receiver = spawn_reader(stream);
+
+    // Button down at the center of the screen, and release at the bottom right corner
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, WIDTH / 2),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, HEIGHT / 2),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, WIDTH),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, HEIGHT),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 0),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            WindowEvent::PointerPressed {
+                position: LogicalPosition { x: 0.5, y: 0.5 },
+                button: PointerEventButton::Left
+            },
+            WindowEvent::PointerReleased {
+                position: LogicalPosition { x: 1.0, y: 1.0 },
+                button: PointerEventButton::Left
+            },
+        ],
+        collect_events(&receiver)
+    );
+}
+
+#[test]
+fn test_release_at_press() {
+    let (mut vdev, dev_path) = create_touch_device("test_release_at_press");
+    let stream = SlintEventsWrapper::with_config(dev_path, Config::new(1.0).release_at_press(true))
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+    let receiver = spawn_reader(stream);
+
+    // Button down at (120, 12)
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    // Button up after drifting to (125, 15)
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 125),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 15),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 0),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            WindowEvent::PointerPressed {
+                position: LogicalPosition { x: 120.0, y: 12.0 },
+                button: PointerEventButton::Left
+            },
+            WindowEvent::PointerReleased {
+                position: LogicalPosition { x: 120.0, y: 12.0 },
+                button: PointerEventButton::Left
+            },
+        ],
+        collect_events(&receiver)
+    );
+}
+
+#[test]
+fn test_has_pending() {
+    let (mut vdev, dev_path) = create_touch_device("test_has_pending");
+    let mut stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+
+    assert!(!stream.has_pending().unwrap());
+
+    vdev.emit(&[InputEvent::new(
+        EventType::KEY.0,
+        KeyCode::BTN_TOUCH.code(),
+        1,
+    )])
+    .unwrap();
+    std::thread::sleep(Duration::from_millis(50));
+
+    // Checking does not consume the input
+    assert!(stream.has_pending().unwrap());
+    assert!(stream.has_pending().unwrap());
+    assert_eq!(1, stream.fetch_events().count());
+    assert!(!stream.has_pending().unwrap());
+}
+
+#[test]
+fn test_swap_axes() {
+    let (mut vdev, dev_path) = create_touch_device("test_swap_axes");
+    let config = Config::new(1.0)
+        .swap_axes(true)
+        .coordinate_mode(CoordinateMode::Normalized {
+            width: 1.0,
+            height: 1.0,
+        });
+    let stream = SlintEventsWrapper::with_config(dev_path, config)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+    let receiver = spawn_reader(stream);
+
+    // The device's Y axis is the X position, normalized against the Y axis range
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, WIDTH / 2),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, HEIGHT / 4),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        vec![WindowEvent::PointerPressed {
+            position: LogicalPosition { x: 0.25, y: 0.5 },
+            button: PointerEventButton::Left
+        }],
+        collect_events(&receiver)
+    );
+}
+
+#[test]
+fn test_fetch_events_into() {
+    let (mut vdev, dev_path) = create_touch_device("test_fetch_events_into");
+    let mut stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+
+    let mut buf = vec![WindowEvent::PointerExited];
+    // Button down at (120, 12), then move to (122, 13)
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 122),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 13),
+    ])
+    .unwrap();
+    std::thread::sleep(Duration::from_millis(50));
+
+    stream.fetch_events_into(&mut buf).unwrap();
+    assert_eq!(
+        vec![
+            WindowEvent::PointerPressed {
+                position: LogicalPosition { x: 120.0, y: 12.0 },
+                button: PointerEventButton::Left
+            },
+            WindowEvent::PointerMoved {
+                position: LogicalPosition { x: 122.0, y: 13.0 }
+            },
+        ],
+        buf
+    );
+}
+
+#[test]
+fn test_reset_state() {
+    let (mut vdev, dev_path) = create_touch_device("test_reset_state");
+    let mut stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 200),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 100),
+    ])
+    .unwrap();
+    assert_eq!(1, stream.fetch_events().count());
+
+    stream.reset_state();
+
+    // The device does not report X again, so it is read from the device on reset
+    vdev.emit(&[InputEvent::new(
+        EventType::ABSOLUTE.0,
+        AbsoluteAxisCode::ABS_Y.0,
+        50,
+    )])
+    .unwrap();
+    assert_eq!(
+        vec![WindowEvent::PointerMoved {
+            position: LogicalPosition { x: 200.0, y: 50.0 }
+        }],
+        stream.fetch_events().collect::<Vec<_>>()
+    );
+
+    // A touch moving only along Y is pressed and released
+    stream.reset_state();
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 60),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    vdev.emit(&[InputEvent::new(
+        EventType::KEY.0,
+        KeyCode::BTN_TOUCH.code(),
+        0,
+    )])
+    .unwrap();
+    let receiver = spawn_reader(stream);
+    assert_eq!(
+        vec![
+            WindowEvent::PointerPressed {
+                position: LogicalPosition { x: 200.0, y: 60.0 },
+                button: PointerEventButton::Left
+            },
+            WindowEvent::PointerReleased {
+                position: LogicalPosition { x: 200.0, y: 60.0 },
+                button: PointerEventButton::Left
+            },
+        ],
+        collect_events(&receiver)
+    );
+}
+
+#[test]
+fn test_events_until_shutdown() {
+    let (mut vdev, dev_path) = create_touch_device("test_events_until_shutdown");
+    let mut stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+    let shutdown = stream.shutdown_handle().unwrap();
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let reader = std::thread::spawn(move || {
+        for event in stream.events_until_shutdown() {
+            sender.send(event).unwrap();
+        }
+    });
+
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    assert_eq!(1, collect_events(&receiver).len());
+
+    // The reader is now blocked waiting for input, and should wake and exit
+    shutdown.shutdown();
+    let start = std::time::Instant::now();
+    while !reader.is_finished() {
+        assert!(start.elapsed() < Duration::from_secs(1));
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    reader.join().unwrap();
+}
+
+#[test]
+fn test_from_fd() {
+    let (mut vdev, dev_path) = create_touch_device("test_from_fd");
+    let file = std::fs::File::open(&dev_path)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+    let mut stream = SlintEventsWrapper::from_fd(file.into(), 1.0).unwrap();
+    assert!(stream.path().as_os_str().is_empty());
+    assert!(matches!(
+        stream.reconnect(),
+        Err(Error::Io(e)) if e.kind() == std::io::ErrorKind::Unsupported
+    ));
+    let receiver = spawn_reader(stream);
+
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        vec![WindowEvent::PointerPressed {
+            position: LogicalPosition { x: 120.0, y: 12.0 },
+            button: PointerEventButton::Left
+        }],
+        collect_events(&receiver)
+    );
+}
+
+#[test]
+fn test_device_not_found() {
+    let path = "/dev/input/no-such-device";
+    assert!(matches!(
+        SlintEventsWrapper::new(path, 1.0),
+        Err(Error::DeviceNotFound(p)) if p.as_os_str() == path
+    ));
+}
+
+#[test]
+fn test_list_devices() {
+    let (_vdev, dev_path) = create_touch_device("test_list_devices");
+
+    let devices = slint_evdev_input::list_devices().unwrap();
+    let info = devices
+        .iter()
+        .find(|info| info.path == dev_path)
+        .expect("Virtual device not listed. Do you have permissions?");
+    assert_eq!(Some("test_list_devices"), info.name.as_deref());
+    assert!(info.is_touchscreen());
+    assert!(!info.has_rel);
+    assert!(!info.is_keyboard);
+}
+
+#[test]
+fn test_config_handle() {
+    let (mut vdev, dev_path) = create_touch_device("test_config_handle");
+    let mut stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+    let config = stream.config_handle();
+    let receiver = spawn_reader(stream);
+
+    // Button down at (120, 12)
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    let pressed = collect_events(&receiver);
+
+    // The reader is blocked, and picks up the change with its next read
+    config.update(|config| config.scale_factor(2.0));
+    vdev.emit(&[InputEvent::new(
+        EventType::KEY.0,
+        KeyCode::BTN_TOUCH.code(),
+        0,
+    )])
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            WindowEvent::PointerPressed {
+                position: LogicalPosition { x: 120.0, y: 12.0 },
+                button: PointerEventButton::Left
+            },
+            WindowEvent::PointerReleased {
+                position: LogicalPosition { x: 60.0, y: 6.0 },
+                button: PointerEventButton::Left
+            },
+        ],
+        [pressed, collect_events(&receiver)].concat()
+    );
+}
+
+#[test]
+fn test_logical_to_raw() {
+    let (_vdev, dev_path) = create_touch_device("test_logical_to_raw");
+
+    let stream = SlintEventsWrapper::new(&dev_path, 2.0)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+    assert_eq!(
+        (120, 12),
+        stream.logical_to_raw(LogicalPosition { x: 60.0, y: 6.0 })
+    );
+
+    let config = Config::new(1.0)
+        .swap_axes(true)
+        .coordinate_mode(CoordinateMode::Normalized {
+            width: 1.0,
+            height: 1.0,
+        });
+    let stream = SlintEventsWrapper::with_config(&dev_path, config).unwrap();
+    // With swapped axes, the logical X position is read from the device's Y axis
+    assert_eq!(
+        (WIDTH / 4, HEIGHT / 2),
+        stream.logical_to_raw(LogicalPosition { x: 0.5, y: 0.25 })
+    );
+}
+
+#[test]
+fn test_replay_state() {
+    let (mut vdev, dev_path) = create_touch_device("test_replay_state");
+    let mut stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+    assert_eq!(None, stream.replay_state());
+
+    // Button down at (120, 12), then move to (122, 13)
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 122),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 13),
+    ])
+    .unwrap();
+    std::thread::sleep(Duration::from_millis(50));
+    while stream.has_pending().unwrap() {
+        stream.fetch_events().for_each(drop);
+    }
+
+    assert_eq!(
+        Some(WindowEvent::PointerPressed {
+            position: LogicalPosition { x: 122.0, y: 13.0 },
+            button: PointerEventButton::Left
+        }),
+        stream.replay_state()
+    );
+
+    // Button up
+    vdev.emit(&[InputEvent::new(
+        EventType::KEY.0,
+        KeyCode::BTN_TOUCH.code(),
+        0,
+    )])
+    .unwrap();
+    stream.fetch_events().for_each(drop);
+    assert_eq!(None, stream.replay_state());
+}
+
+#[test]
+fn test_unexpected_device_rejected() {
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::KEY_A);
+    keys.insert(KeyCode::KEY_ENTER);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_unexpected_device_rejected")
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(200));
+
+    assert!(matches!(
+        SlintEventsWrapper::new(&dev_path, 1.0),
+        Err(Error::MissingAxis(AbsoluteAxisCode::ABS_X))
+    ));
+    let config = Config::new(1.0).allow_any_device(true);
+    assert!(SlintEventsWrapper::with_config(&dev_path, config).is_ok());
+}
+
+#[test]
+fn test_fetch_sequenced() {
+    let (mut vdev, dev_path) = create_touch_device("test_fetch_sequenced");
+    let mut stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+
+    let mut sequences = Vec::new();
+    for x in [100, 110, 120] {
+        vdev.emit(&[
+            InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, x),
+            InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 50),
+        ])
+        .unwrap();
+        sequences.extend(
+            stream
+                .fetch_sequenced()
+                .unwrap()
+                .map(|event| event.sequence),
+        );
+    }
+    assert_eq!(vec![0, 1, 2], sequences);
+}
+
+#[test]
+fn test_drain_latest() {
+    let (mut vdev, dev_path) = create_touch_device("test_drain_latest");
+    let mut stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+
+    assert_eq!(None, stream.drain_latest().unwrap());
+
+    // A drag begun within the backlog is reported as a press at its final position
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 100),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 50),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    for x in 101..150 {
+        vdev.emit(&[InputEvent::new(
+            EventType::ABSOLUTE.0,
+            AbsoluteAxisCode::ABS_X.0,
+            x,
+        )])
+        .unwrap();
+    }
+    std::thread::sleep(Duration::from_millis(50));
+    assert_eq!(
+        Some(WindowEvent::PointerPressed {
+            position: LogicalPosition::new(149.0, 50.0),
+            button: PointerEventButton::Left,
+        }),
+        stream.drain_latest().unwrap()
+    );
+
+    // Continuing the drag is reported as a single move
+    for y in 51..100 {
+        vdev.emit(&[InputEvent::new(
+            EventType::ABSOLUTE.0,
+            AbsoluteAxisCode::ABS_Y.0,
+            y,
+        )])
+        .unwrap();
+    }
+    std::thread::sleep(Duration::from_millis(50));
+    assert_eq!(
+        Some(WindowEvent::PointerMoved {
+            position: LogicalPosition::new(149.0, 99.0),
+        }),
+        stream.drain_latest().unwrap()
+    );
+
+    // Lifting the contact is reported as the release
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 160),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 0),
+    ])
+    .unwrap();
+    std::thread::sleep(Duration::from_millis(50));
+    assert_eq!(
+        Some(WindowEvent::PointerReleased {
+            position: LogicalPosition::new(160.0, 99.0),
+            button: PointerEventButton::Left,
+        }),
+        stream.drain_latest().unwrap()
+    );
+}
+
+#[test]
+fn test_position_read_on_open() {
+    let (mut vdev, dev_path) = create_touch_device("test_position_read_on_open");
+
+    // The position is reported before the device is opened
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+    ])
+    .unwrap();
+    std::thread::sleep(Duration::from_millis(50));
+
+    let mut stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+
+    // A press without coordinates is reported at the device's position
+    vdev.emit(&[InputEvent::new(
+        EventType::KEY.0,
+        KeyCode::BTN_TOUCH.code(),
+        1,
+    )])
+    .unwrap();
+    assert_eq!(
+        vec![WindowEvent::PointerPressed {
+            position: LogicalPosition { x: 120.0, y: 12.0 },
+            button: PointerEventButton::Left,
+        }],
+        stream.fetch_events().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_spawn_into_channel() {
+    let (mut vdev, dev_path) = create_touch_device("test_spawn_into_channel");
+    let stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let reader = stream.spawn_into_channel(sender);
+
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    assert_eq!(
+        WindowEvent::PointerPressed {
+            position: LogicalPosition { x: 120.0, y: 12.0 },
+            button: PointerEventButton::Left,
+        },
+        receiver.recv_timeout(Duration::from_millis(200)).unwrap()
+    );
+
+    // The reader ends once it finds the receiver dropped
+    drop(receiver);
+    vdev.emit(&[InputEvent::new(
+        EventType::ABSOLUTE.0,
+        AbsoluteAxisCode::ABS_X.0,
+        130,
+    )])
+    .unwrap();
+    reader.join().unwrap().unwrap();
+}
+
+#[test]
+fn test_spawn_into_channel_with_priority() {
+    let (mut vdev, dev_path) = create_touch_device("test_spawn_into_channel_with_priority");
+    let stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .expect("Failed opening {dev_path:?}. DO you have permisssions?");
+
+    // Events are forwarded whether or not the priority could be raised
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let reader = stream.spawn_into_channel_with_priority(sender, ThreadPriority::Fifo(10));
+
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    assert_eq!(
+        WindowEvent::PointerPressed {
+            position: LogicalPosition { x: 120.0, y: 12.0 },
+            button: PointerEventButton::Left,
+        },
+        receiver.recv_timeout(Duration::from_millis(200)).unwrap()
+    );
+
+    drop(receiver);
+    vdev.emit(&[InputEvent::new(
+        EventType::ABSOLUTE.0,
+        AbsoluteAxisCode::ABS_X.0,
+        130,
+    )])
+    .unwrap();
+    reader.join().unwrap().unwrap();
+}
@@ -108,3 +108,1292 @@ async fn test_event_stream() {
         slint_events
     );
 }
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_coalesce_moves() {
+    use std::time::Duration;
+
+    use evdev::{
+        AbsInfo, AbsoluteAxisCode, AttributeSet, EventType, InputEvent, KeyCode, UinputAbsSetup,
+        uinput::VirtualDevice,
+    };
+    use slint_evdev_input::{Config, SlintEventsWrapper};
+
+    const WIDTH: i32 = 320;
+    const HEIGHT: i32 = 240;
+    use slint::{
+        LogicalPosition,
+        platform::{PointerEventButton, WindowEvent},
+    };
+
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_coalesce_moves")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(100));
+
+    println!("Opening {dev_path:?}");
+    let config = Config::new(1.0).coalesce_interval(Some(Duration::from_millis(100)));
+    let mut stream = SlintEventsWrapper::with_config(dev_path, config)
+        .unwrap()
+        .into_event_stream()
+        .expect("Failed to open device. Do you have permissions to access /dev/input/eventX?");
+
+    // Button down at (120, 12)
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    // A burst of moves, ending at (124, 12)
+    for x in 121..125 {
+        vdev.emit(&[InputEvent::new(
+            EventType::ABSOLUTE.0,
+            AbsoluteAxisCode::ABS_X.0,
+            x,
+        )])
+        .unwrap();
+    }
+    // Button up
+    vdev.emit(&[InputEvent::new(
+        EventType::KEY.0,
+        KeyCode::BTN_TOUCH.code(),
+        0,
+    )])
+    .unwrap();
+
+    let mut slint_events = Vec::new();
+    // Read events until we timeout
+    while let Ok(event) =
+        tokio::time::timeout(Duration::from_millis(200), stream.next_event()).await
+    {
+        slint_events.push(event.unwrap());
+    }
+
+    assert_eq!(
+        vec![
+            WindowEvent::PointerPressed {
+                position: LogicalPosition { x: 120.0, y: 12.0 },
+                button: PointerEventButton::Left
+            },
+            WindowEvent::PointerMoved {
+                position: LogicalPosition { x: 124.0, y: 12.0 }
+            },
+            WindowEvent::PointerReleased {
+                position: LogicalPosition { x: 124.0, y: 12.0 },
+                button: PointerEventButton::Left
+            },
+        ],
+        slint_events
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_max_move_rate() {
+    use std::time::Duration;
+
+    use evdev::{
+        AbsInfo, AbsoluteAxisCode, AttributeSet, EventType, InputEvent, KeyCode, UinputAbsSetup,
+        uinput::VirtualDevice,
+    };
+    use slint_evdev_input::{Config, SlintEventsWrapper};
+
+    const WIDTH: i32 = 320;
+    const HEIGHT: i32 = 240;
+    use slint::{
+        LogicalPosition,
+        platform::{PointerEventButton, WindowEvent},
+    };
+
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_max_move_rate")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(100));
+
+    println!("Opening {dev_path:?}");
+    let config = Config::new(1.0).max_move_rate(Some(10.0));
+    let mut stream = SlintEventsWrapper::with_config(dev_path, config)
+        .unwrap()
+        .into_event_stream()
+        .expect("Failed to open device. Do you have permissions to access /dev/input/eventX?");
+
+    // Button down at (120, 12)
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    // A burst of moves, ending at (124, 12)
+    for x in 121..125 {
+        vdev.emit(&[InputEvent::new(
+            EventType::ABSOLUTE.0,
+            AbsoluteAxisCode::ABS_X.0,
+            x,
+        )])
+        .unwrap();
+    }
+
+    let pressed = stream.next_event().await.unwrap();
+    // The first move is not limited
+    let first_move = stream.next_event().await.unwrap();
+    let start = tokio::time::Instant::now();
+    // The rest of the burst is held until the next allowed instant
+    let held_move = stream.next_event().await.unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(90));
+
+    // Button up, which is not delayed
+    vdev.emit(&[InputEvent::new(
+        EventType::KEY.0,
+        KeyCode::BTN_TOUCH.code(),
+        0,
+    )])
+    .unwrap();
+    let released = tokio::time::timeout(Duration::from_millis(50), stream.next_event())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        vec![
+            WindowEvent::PointerPressed {
+                position: LogicalPosition { x: 120.0, y: 12.0 },
+                button: PointerEventButton::Left
+            },
+            WindowEvent::PointerMoved {
+                position: LogicalPosition { x: 121.0, y: 12.0 }
+            },
+            WindowEvent::PointerMoved {
+                position: LogicalPosition { x: 124.0, y: 12.0 }
+            },
+            WindowEvent::PointerReleased {
+                position: LogicalPosition { x: 124.0, y: 12.0 },
+                button: PointerEventButton::Left
+            },
+        ],
+        vec![pressed, first_move, held_move, released]
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_ready_and_drain() {
+    use std::time::Duration;
+
+    use evdev::{
+        AbsInfo, AbsoluteAxisCode, AttributeSet, EventType, InputEvent, KeyCode, UinputAbsSetup,
+        uinput::VirtualDevice,
+    };
+    use slint_evdev_input::SlintEventsWrapper;
+
+    const WIDTH: i32 = 320;
+    const HEIGHT: i32 = 240;
+    use slint::{
+        LogicalPosition,
+        platform::{PointerEventButton, WindowEvent},
+    };
+
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_ready_and_drain")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(100));
+
+    println!("Opening {dev_path:?}");
+    let mut stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .unwrap()
+        .into_event_stream()
+        .expect("Failed to open device. Do you have permissions to access /dev/input/eventX?");
+
+    // Nothing is ready yet
+    assert!(stream.drain().unwrap().is_empty());
+
+    // Button down at (120, 12), a move, and button up, all written before reading
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    vdev.emit(&[InputEvent::new(
+        EventType::ABSOLUTE.0,
+        AbsoluteAxisCode::ABS_X.0,
+        122,
+    )])
+    .unwrap();
+    vdev.emit(&[InputEvent::new(
+        EventType::KEY.0,
+        KeyCode::BTN_TOUCH.code(),
+        0,
+    )])
+    .unwrap();
+
+    tokio::time::timeout(Duration::from_millis(200), stream.ready())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        vec![
+            WindowEvent::PointerPressed {
+                position: LogicalPosition { x: 120.0, y: 12.0 },
+                button: PointerEventButton::Left
+            },
+            WindowEvent::PointerMoved {
+                position: LogicalPosition { x: 122.0, y: 12.0 }
+            },
+            WindowEvent::PointerReleased {
+                position: LogicalPosition { x: 122.0, y: 12.0 },
+                button: PointerEventButton::Left
+            },
+        ],
+        stream.drain().unwrap()
+    );
+    assert!(stream.drain().unwrap().is_empty());
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_next_events() {
+    use std::time::Duration;
+
+    use evdev::{
+        AbsInfo, AbsoluteAxisCode, AttributeSet, EventType, InputEvent, KeyCode, UinputAbsSetup,
+        uinput::VirtualDevice,
+    };
+    use slint_evdev_input::SlintEventsWrapper;
+
+    const WIDTH: i32 = 320;
+    const HEIGHT: i32 = 240;
+    use slint::{
+        LogicalPosition,
+        platform::{PointerEventButton, WindowEvent},
+    };
+
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_next_events")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(100));
+
+    println!("Opening {dev_path:?}");
+    let mut stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .unwrap()
+        .into_event_stream()
+        .expect("Failed to open device. Do you have permissions to access /dev/input/eventX?");
+
+    // A press, two moves, and a release, all written before reading
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    for x in [122, 124] {
+        vdev.emit(&[InputEvent::new(
+            EventType::ABSOLUTE.0,
+            AbsoluteAxisCode::ABS_X.0,
+            x,
+        )])
+        .unwrap();
+    }
+    vdev.emit(&[InputEvent::new(
+        EventType::KEY.0,
+        KeyCode::BTN_TOUCH.code(),
+        0,
+    )])
+    .unwrap();
+    std::thread::sleep(Duration::from_millis(20));
+
+    let moved = |x| WindowEvent::PointerMoved {
+        position: LogicalPosition { x, y: 12.0 },
+    };
+    // Nothing is read for an empty batch
+    assert!(stream.next_events(0).await.unwrap().is_empty());
+    // The batch is limited to `max`, and the rest is returned by the next call
+    assert_eq!(
+        vec![
+            WindowEvent::PointerPressed {
+                position: LogicalPosition { x: 120.0, y: 12.0 },
+                button: PointerEventButton::Left
+            },
+            moved(122.0),
+            moved(124.0),
+        ],
+        tokio::time::timeout(Duration::from_millis(200), stream.next_events(3))
+            .await
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        vec![WindowEvent::PointerReleased {
+            position: LogicalPosition { x: 124.0, y: 12.0 },
+            button: PointerEventButton::Left
+        }],
+        tokio::time::timeout(Duration::from_millis(200), stream.next_events(3))
+            .await
+            .unwrap()
+            .unwrap()
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_max_press_duration() {
+    use std::time::Duration;
+
+    use evdev::{
+        AbsInfo, AbsoluteAxisCode, AttributeSet, EventType, InputEvent, KeyCode, UinputAbsSetup,
+        uinput::VirtualDevice,
+    };
+    use slint_evdev_input::{Config, SlintEventsWrapper};
+
+    const WIDTH: i32 = 320;
+    const HEIGHT: i32 = 240;
+    use slint::{
+        LogicalPosition,
+        platform::{PointerEventButton, WindowEvent},
+    };
+
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_max_press_duration")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(100));
+
+    println!("Opening {dev_path:?}");
+    let config = Config::new(1.0).max_press_duration(Some(Duration::from_millis(200)));
+    let mut stream = SlintEventsWrapper::with_config(dev_path, config)
+        .unwrap()
+        .into_event_stream()
+        .expect("Failed to open device. Do you have permissions to access /dev/input/eventX?");
+
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    assert_eq!(
+        WindowEvent::PointerPressed {
+            position: LogicalPosition { x: 120.0, y: 12.0 },
+            button: PointerEventButton::Left
+        },
+        tokio::time::timeout(Duration::from_millis(100), stream.next_event())
+            .await
+            .unwrap()
+            .unwrap()
+    );
+
+    // A move before the timeout restarts it
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    vdev.emit(&[InputEvent::new(
+        EventType::ABSOLUTE.0,
+        AbsoluteAxisCode::ABS_X.0,
+        130,
+    )])
+    .unwrap();
+    assert_eq!(
+        WindowEvent::PointerMoved {
+            position: LogicalPosition { x: 130.0, y: 12.0 }
+        },
+        tokio::time::timeout(Duration::from_millis(100), stream.next_event())
+            .await
+            .unwrap()
+            .unwrap()
+    );
+    assert!(
+        tokio::time::timeout(Duration::from_millis(150), stream.next_event())
+            .await
+            .is_err()
+    );
+
+    // Held still for too long, the contact is released although still down
+    assert_eq!(
+        WindowEvent::PointerReleased {
+            position: LogicalPosition { x: 130.0, y: 12.0 },
+            button: PointerEventButton::Left
+        },
+        tokio::time::timeout(Duration::from_millis(200), stream.next_event())
+            .await
+            .unwrap()
+            .unwrap()
+    );
+
+    // The eventual lift is not reported again
+    vdev.emit(&[InputEvent::new(
+        EventType::KEY.0,
+        KeyCode::BTN_TOUCH.code(),
+        0,
+    )])
+    .unwrap();
+    assert!(
+        tokio::time::timeout(Duration::from_millis(100), stream.next_event())
+            .await
+            .is_err()
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_max_press_duration_drain() {
+    use std::time::Duration;
+
+    use evdev::{
+        AbsInfo, AbsoluteAxisCode, AttributeSet, EventType, InputEvent, KeyCode, UinputAbsSetup,
+        uinput::VirtualDevice,
+    };
+    use slint_evdev_input::{Config, SlintEventsWrapper};
+
+    const WIDTH: i32 = 320;
+    const HEIGHT: i32 = 240;
+    use slint::{
+        LogicalPosition,
+        platform::{PointerEventButton, WindowEvent},
+    };
+
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_max_press_duration_drain")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(100));
+
+    println!("Opening {dev_path:?}");
+    let config = Config::new(1.0).max_press_duration(Some(Duration::from_millis(200)));
+    let mut stream = SlintEventsWrapper::with_config(dev_path, config)
+        .unwrap()
+        .into_event_stream()
+        .expect("Failed to open device. Do you have permissions to access /dev/input/eventX?");
+
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    tokio::time::timeout(Duration::from_millis(100), stream.ready())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        vec![WindowEvent::PointerPressed {
+            position: LogicalPosition { x: 120.0, y: 12.0 },
+            button: PointerEventButton::Left
+        }],
+        stream.drain().unwrap()
+    );
+
+    // Held still for too long, the contact is released by the batched reads too
+    tokio::time::timeout(Duration::from_millis(300), stream.ready())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        vec![WindowEvent::PointerReleased {
+            position: LogicalPosition { x: 120.0, y: 12.0 },
+            button: PointerEventButton::Left
+        }],
+        stream.drain().unwrap()
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_multi_event_stream() {
+    use std::time::Duration;
+
+    use evdev::{
+        AbsInfo, AbsoluteAxisCode, AttributeSet, EventType, InputEvent, KeyCode, UinputAbsSetup,
+        uinput::VirtualDevice,
+    };
+    use slint_evdev_input::{SlintEventsWrapper, tokio::MultiEventStream};
+
+    const WIDTH: i32 = 320;
+    const HEIGHT: i32 = 240;
+    use slint::{
+        LogicalPosition,
+        platform::{PointerEventButton, WindowEvent},
+    };
+
+    let create_device = |name: &str| {
+        let mut keys = AttributeSet::<KeyCode>::new();
+        keys.insert(KeyCode::BTN_TOUCH);
+        let mut vdev = VirtualDevice::builder()
+            .unwrap()
+            .name(name)
+            .with_absolute_axis(&UinputAbsSetup::new(
+                AbsoluteAxisCode::ABS_X,
+                AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+            ))
+            .unwrap()
+            .with_absolute_axis(&UinputAbsSetup::new(
+                AbsoluteAxisCode::ABS_Y,
+                AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+            ))
+            .unwrap()
+            .with_keys(&keys)
+            .unwrap()
+            .build()
+            .unwrap();
+        let dev_path = vdev
+            .enumerate_dev_nodes_blocking()
+            .unwrap()
+            .map(|p| p.unwrap())
+            .next()
+            .unwrap();
+        (vdev, dev_path)
+    };
+    let (mut vdev_a, path_a) = create_device("test_multi_event_stream_a");
+    let (mut vdev_b, path_b) = create_device("test_multi_event_stream_b");
+
+    std::thread::sleep(Duration::from_millis(100));
+
+    let mut streams = MultiEventStream::new();
+    for path in [path_a, path_b] {
+        streams.push(
+            SlintEventsWrapper::new(path, 1.0)
+                .unwrap()
+                .into_event_stream()
+                .expect(
+                    "Failed to open device. Do you have permissions to access /dev/input/eventX?",
+                ),
+        );
+    }
+
+    let press = |x, y| {
+        [
+            InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, x),
+            InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, y),
+            InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+        ]
+    };
+    let release = [InputEvent::new(
+        EventType::KEY.0,
+        KeyCode::BTN_TOUCH.code(),
+        0,
+    )];
+    // Both devices are ready at once, with device 0 written first
+    vdev_a.emit(&press(120, 12)).unwrap();
+    vdev_a.emit(&release).unwrap();
+    vdev_b.emit(&press(200, 100)).unwrap();
+
+    let mut events = Vec::new();
+    for _ in 0..3 {
+        let (index, event) = tokio::time::timeout(Duration::from_millis(200), streams.next_event())
+            .await
+            .unwrap();
+        events.push((index, event.unwrap()));
+    }
+
+    // Devices take turns, so device 1's press is not held behind device 0's release
+    assert_eq!(
+        vec![
+            (
+                0,
+                WindowEvent::PointerPressed {
+                    position: LogicalPosition { x: 120.0, y: 12.0 },
+                    button: PointerEventButton::Left
+                }
+            ),
+            (
+                1,
+                WindowEvent::PointerPressed {
+                    position: LogicalPosition { x: 200.0, y: 100.0 },
+                    button: PointerEventButton::Left
+                }
+            ),
+            (
+                0,
+                WindowEvent::PointerReleased {
+                    position: LogicalPosition { x: 120.0, y: 12.0 },
+                    button: PointerEventButton::Left
+                }
+            ),
+        ],
+        events
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_multi_event_stream_coalesce() {
+    use std::time::Duration;
+
+    use evdev::{
+        AbsInfo, AbsoluteAxisCode, AttributeSet, EventType, InputEvent, KeyCode, UinputAbsSetup,
+        uinput::VirtualDevice,
+    };
+    use slint_evdev_input::{Config, SlintEventsWrapper, tokio::MultiEventStream};
+
+    const WIDTH: i32 = 320;
+    const HEIGHT: i32 = 240;
+    use slint::{
+        LogicalPosition,
+        platform::{PointerEventButton, WindowEvent},
+    };
+
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_multi_event_stream_coalesce")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(100));
+
+    let config = Config::new(1.0).coalesce_interval(Some(Duration::from_millis(100)));
+    let mut streams = MultiEventStream::new();
+    streams.push(
+        SlintEventsWrapper::with_config(dev_path, config)
+            .unwrap()
+            .into_event_stream()
+            .expect("Failed to open device. Do you have permissions to access /dev/input/eventX?"),
+    );
+
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    for x in [122, 124] {
+        vdev.emit(&[InputEvent::new(
+            EventType::ABSOLUTE.0,
+            AbsoluteAxisCode::ABS_X.0,
+            x,
+        )])
+        .unwrap();
+    }
+
+    let mut events = Vec::new();
+    for _ in 0..2 {
+        let (index, event) = tokio::time::timeout(Duration::from_millis(300), streams.next_event())
+            .await
+            .unwrap();
+        events.push((index, event.unwrap()));
+    }
+
+    // The device's moves are coalesced as they would be by its own stream
+    assert_eq!(
+        vec![
+            (
+                0,
+                WindowEvent::PointerPressed {
+                    position: LogicalPosition { x: 120.0, y: 12.0 },
+                    button: PointerEventButton::Left
+                }
+            ),
+            (
+                0,
+                WindowEvent::PointerMoved {
+                    position: LogicalPosition { x: 124.0, y: 12.0 }
+                }
+            ),
+        ],
+        events
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_event_stream_flush_on_read() {
+    use std::time::Duration;
+
+    use evdev::{
+        AbsInfo, AbsoluteAxisCode, AttributeSet, EventType, InputEvent, KeyCode,
+        SynchronizationCode, UinputAbsSetup, uinput::VirtualDevice,
+    };
+    use slint_evdev_input::{Config, SlintEventsWrapper};
+
+    const WIDTH: i32 = 320;
+    const HEIGHT: i32 = 240;
+    use slint::{
+        LogicalPosition,
+        platform::{PointerEventButton, WindowEvent},
+    };
+
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_event_stream_flush_on_read")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(100));
+
+    // uinput always follows events with SYN_REPORT, so a driver which never reports it is
+    // simulated by ending frames only on SYN_MT_REPORT, which is never sent
+    let config = Config::new(1.0)
+        .frame_end_codes([SynchronizationCode::SYN_MT_REPORT])
+        .flush_on_read(true);
+    let mut stream = SlintEventsWrapper::with_config(dev_path, config)
+        .unwrap()
+        .into_event_stream()
+        .expect("Failed to open device. Do you have permissions to access /dev/input/eventX?");
+
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    assert_eq!(
+        WindowEvent::PointerPressed {
+            position: LogicalPosition { x: 120.0, y: 12.0 },
+            button: PointerEventButton::Left
+        },
+        tokio::time::timeout(Duration::from_millis(200), stream.next_event())
+            .await
+            .unwrap()
+            .unwrap()
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_key_repeat() {
+    use std::time::Duration;
+
+    use evdev::{
+        AbsInfo, AbsoluteAxisCode, AttributeSet, EventType, InputEvent, KeyCode, UinputAbsSetup,
+        uinput::VirtualDevice,
+    };
+    use slint_evdev_input::{Config, SlintEventsWrapper};
+
+    const WIDTH: i32 = 320;
+    const HEIGHT: i32 = 240;
+    use slint::platform::{Key, WindowEvent};
+
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_key_repeat")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(100));
+
+    let config = Config::new(1.0)
+        .contact_as_key(Some(Key::Return.into()))
+        .key_repeat(Some((Duration::from_millis(50), Duration::from_millis(20))));
+    let mut stream = SlintEventsWrapper::with_config(dev_path, config)
+        .unwrap()
+        .into_event_stream()
+        .expect("Failed to open device. Do you have permissions to access /dev/input/eventX?");
+
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+
+    let mut next = async || {
+        tokio::time::timeout(Duration::from_millis(200), stream.next_event())
+            .await
+            .unwrap()
+            .unwrap()
+    };
+    let text = || Key::Return.into();
+    assert_eq!(WindowEvent::KeyPressed { text: text() }, next().await);
+    assert_eq!(WindowEvent::KeyPressRepeated { text: text() }, next().await);
+    assert_eq!(WindowEvent::KeyPressRepeated { text: text() }, next().await);
+
+    vdev.emit(&[InputEvent::new(
+        EventType::KEY.0,
+        KeyCode::BTN_TOUCH.code(),
+        0,
+    )])
+    .unwrap();
+    // The release may follow further repeats which were due before it was read
+    loop {
+        match next().await {
+            WindowEvent::KeyPressRepeated { .. } => (),
+            event => {
+                assert_eq!(WindowEvent::KeyReleased { text: text() }, event);
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_event_queue_limit() {
+    use std::time::Duration;
+
+    use evdev::{
+        AbsInfo, AbsoluteAxisCode, AttributeSet, EventType, InputEvent, KeyCode, UinputAbsSetup,
+        uinput::VirtualDevice,
+    };
+    use slint_evdev_input::{Config, OverflowPolicy, SlintEventsWrapper};
+
+    const WIDTH: i32 = 320;
+    const HEIGHT: i32 = 240;
+    use slint::{
+        LogicalPosition,
+        platform::{PointerEventButton, WindowEvent},
+    };
+
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_event_queue_limit")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(100));
+
+    let config = Config::new(1.0).event_queue_limit(2, OverflowPolicy::KeepLatestMove);
+    let mut stream = SlintEventsWrapper::with_config(dev_path, config)
+        .unwrap()
+        .into_event_stream()
+        .expect("Failed to open device. Do you have permissions to access /dev/input/eventX?");
+
+    // A drag which is complete before the consumer reads any of it
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 100),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 50),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    for x in 101..=120 {
+        vdev.emit(&[InputEvent::new(
+            EventType::ABSOLUTE.0,
+            AbsoluteAxisCode::ABS_X.0,
+            x,
+        )])
+        .unwrap();
+    }
+    vdev.emit(&[InputEvent::new(
+        EventType::KEY.0,
+        KeyCode::BTN_TOUCH.code(),
+        0,
+    )])
+    .unwrap();
+    std::thread::sleep(Duration::from_millis(50));
+
+    let mut events = Vec::new();
+    for _ in 0..3 {
+        events.push(
+            tokio::time::timeout(Duration::from_millis(200), stream.next_event())
+                .await
+                .unwrap()
+                .unwrap(),
+        );
+    }
+    // Only the latest move is kept, and the press and release are never dropped
+    assert_eq!(
+        vec![
+            WindowEvent::PointerPressed {
+                position: LogicalPosition::new(100.0, 50.0),
+                button: PointerEventButton::Left,
+            },
+            WindowEvent::PointerMoved {
+                position: LogicalPosition::new(120.0, 50.0),
+            },
+            WindowEvent::PointerReleased {
+                position: LogicalPosition::new(120.0, 50.0),
+                button: PointerEventButton::Left,
+            },
+        ],
+        events
+    );
+    assert_eq!(19, stream.metrics().moves_coalesced);
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_spawn_into_channel() {
+    use std::time::Duration;
+
+    use evdev::{
+        AbsInfo, AbsoluteAxisCode, AttributeSet, EventType, InputEvent, KeyCode, UinputAbsSetup,
+        uinput::VirtualDevice,
+    };
+    use slint_evdev_input::SlintEventsWrapper;
+
+    const WIDTH: i32 = 320;
+    const HEIGHT: i32 = 240;
+    use slint::{
+        LogicalPosition,
+        platform::{PointerEventButton, WindowEvent},
+    };
+
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_spawn_into_channel")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, WIDTH, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, HEIGHT, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+
+    std::thread::sleep(Duration::from_millis(100));
+
+    let stream = SlintEventsWrapper::new(dev_path, 1.0)
+        .unwrap()
+        .into_event_stream()
+        .expect("Failed to open device. Do you have permissions to access /dev/input/eventX?");
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(8);
+    let reader = stream.spawn_into_channel(sender);
+
+    vdev.emit(&[
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_X.0, 120),
+        InputEvent::new(EventType::ABSOLUTE.0, AbsoluteAxisCode::ABS_Y.0, 12),
+        InputEvent::new(EventType::KEY.0, KeyCode::BTN_TOUCH.code(), 1),
+    ])
+    .unwrap();
+    assert_eq!(
+        Some(WindowEvent::PointerPressed {
+            position: LogicalPosition { x: 120.0, y: 12.0 },
+            button: PointerEventButton::Left,
+        }),
+        tokio::time::timeout(Duration::from_millis(200), receiver.recv())
+            .await
+            .unwrap()
+    );
+
+    // The task ends once it finds the receiver dropped
+    drop(receiver);
+    vdev.emit(&[InputEvent::new(
+        EventType::ABSOLUTE.0,
+        AbsoluteAxisCode::ABS_X.0,
+        130,
+    )])
+    .unwrap();
+    tokio::time::timeout(Duration::from_millis(200), reader)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn test_event_stream_outside_runtime() {
+    use std::time::Duration;
+
+    use evdev::{
+        AbsInfo, AbsoluteAxisCode, AttributeSet, KeyCode, UinputAbsSetup, uinput::VirtualDevice,
+    };
+    use slint_evdev_input::{Error, SlintEventsWrapper};
+
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_event_stream_outside_runtime")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, 320, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, 240, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+
+    // Outside of any runtime
+    let wrapper = SlintEventsWrapper::new(&dev_path, 1.0).unwrap();
+    assert!(matches!(wrapper.into_event_stream(), Err(Error::Io(_))));
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+#[should_panic(expected = "IO is disabled")]
+fn test_event_stream_without_io() {
+    use std::time::Duration;
+
+    use evdev::{
+        AbsInfo, AbsoluteAxisCode, AttributeSet, KeyCode, UinputAbsSetup, uinput::VirtualDevice,
+    };
+    use slint_evdev_input::SlintEventsWrapper;
+
+    let mut keys = AttributeSet::<KeyCode>::new();
+    keys.insert(KeyCode::BTN_TOUCH);
+    let mut vdev = VirtualDevice::builder()
+        .unwrap()
+        .name("test_event_stream_without_io")
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_X,
+            AbsInfo::new(0, 0, 320, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_absolute_axis(&UinputAbsSetup::new(
+            AbsoluteAxisCode::ABS_Y,
+            AbsInfo::new(0, 0, 240, 0, 0, 1),
+        ))
+        .unwrap()
+        .with_keys(&keys)
+        .unwrap()
+        .build()
+        .unwrap();
+    let dev_path = vdev
+        .enumerate_dev_nodes_blocking()
+        .unwrap()
+        .map(|p| p.unwrap())
+        .next()
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+
+    // Within a runtime without an I/O driver
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+    let _guard = runtime.enter();
+    let wrapper = SlintEventsWrapper::new(&dev_path, 1.0).unwrap();
+    let _ = wrapper.into_event_stream();
+}